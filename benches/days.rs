@@ -0,0 +1,55 @@
+use std::{hint::black_box, io::Read};
+
+use advent_of_code::{
+    get_cached_input, get_test_input,
+    y2024::{day01::Day1, day02::Day2, day03::Day3, day04::Day4, day05::Day5, day06::Day6},
+    Solution,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Benchmarks against the cached real input when there is one, falling back to
+/// the example input so the benches also run on a fresh checkout.
+fn bench_input(year: u32, day: u32) -> String {
+    get_cached_input(year, day).unwrap_or_else(|| {
+        let mut input = String::new();
+        get_test_input(year, day)
+            .read_to_string(&mut input)
+            .expect("Could not read test input");
+
+        input
+    })
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion, sample_size: usize) {
+    let input = bench_input(S::YEAR, S::DAY);
+    let parsed = S::parse(&input);
+
+    let mut group = c.benchmark_group(format!("y{}-day{}", S::YEAR, S::DAY));
+    group.sample_size(sample_size);
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    // The alternatives are kept to be compared against, so time them as well.
+    for alternative in S::alternatives() {
+        group.bench_function(
+            format!("part{}-{}", alternative.part, alternative.name),
+            |b| b.iter(|| (alternative.solve)(black_box(&parsed))),
+        );
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<Day1>(c, 100);
+    bench_day::<Day2>(c, 100);
+    bench_day::<Day3>(c, 100);
+    bench_day::<Day4>(c, 100);
+    bench_day::<Day5>(c, 100);
+    // The loop search re-simulates the whole map per candidate obstruction.
+    bench_day::<Day6>(c, 10);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
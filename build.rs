@@ -0,0 +1,48 @@
+//! Fingerprints the build for the answer cache, so that every binary built
+//! from the same code shares its cached answers. The profile and features are
+//! part of it too, as they change the timings and allocations that are cached
+//! with the answers.
+
+use std::{env, fs, io, path::Path};
+
+fn main() {
+    let mut files = Vec::new();
+    for path in ["src", "Cargo.toml", "Cargo.lock"] {
+        println!("cargo:rerun-if-changed={path}");
+        collect(Path::new(path), &mut files).expect("could not list the sources");
+    }
+    files.sort();
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(name, _)| name.strip_prefix("CARGO_FEATURE_").map(String::from))
+        .collect();
+    features.sort();
+
+    // 64-bit FNV-1a, as in the cache.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut add = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash = (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    add(env::var("PROFILE").unwrap_or_default().as_bytes());
+    add(features.join(",").as_bytes());
+    for file in &files {
+        add(file.to_string_lossy().as_bytes());
+        add(&fs::read(file).expect("could not read the sources"));
+    }
+
+    println!("cargo:rustc-env=AOC_BUILD={hash:016x}");
+}
+
+fn collect(path: &Path, files: &mut Vec<std::path::PathBuf>) -> io::Result<()> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect(&entry?.path(), files)?;
+        }
+    } else if path.is_file() {
+        files.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::{self, Display},
+};
+
+/// Global allocator that counts the allocations made on each thread. The
+/// library installs it when built with the `count-allocations` feature.
+pub struct CountingAlloc;
+
+struct Counters {
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    current: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    // Counted per thread so that days solved in parallel do not skew each
+    // other. The const initialiser means touching it never allocates.
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            current: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+fn record_alloc(size: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        counters.allocations.set(counters.allocations.get() + 1);
+        counters.bytes.set(counters.bytes.get() + size as u64);
+
+        let current = counters.current.get() + size as i64;
+        counters.current.set(current);
+        counters.peak.set(counters.peak.get().max(current));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        counters.current.set(counters.current.get() - size as i64);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// Allocations made by the current thread while running a closure.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Highest amount of memory held at once, above what was held before.
+    pub peak: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Runs `f`, returning its allocation usage when allocations are counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let (allocations, bytes, current) = COUNTERS.with(|counters| {
+        counters.peak.set(counters.current.get());
+
+        (
+            counters.allocations.get(),
+            counters.bytes.get(),
+            counters.current.get(),
+        )
+    });
+
+    let value = f();
+
+    let usage = COUNTERS.with(|counters| Usage {
+        allocations: counters.allocations.get() - allocations,
+        bytes: counters.bytes.get() - bytes,
+        peak: (counters.peak.get() - current).max(0) as u64,
+    });
+
+    (value, Some(usage))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_bytes;
+
+    #[test]
+    fn format_byte_sizes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn measure_vec_allocations() {
+        let (_, usage) = super::measure(|| {
+            let small = vec![0u8; 100];
+            let large = vec![0u8; 1000];
+            drop(large);
+
+            small
+        });
+        let usage = usage.unwrap();
+
+        assert_eq!(usage.allocations, 2);
+        assert_eq!(usage.bytes, 1100);
+        assert_eq!(usage.peak, 1100);
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::{self, Display};
+
+use crate::ocr;
+
+/// The answer to a part of a puzzle. Integers of any width compare by value
+/// and text is normalised when it is created, so answers computed, recorded
+/// and submitted in different forms still compare equal. Text that is a
+/// canonical integer, e.g. a solution returning `n.to_string()`, becomes a
+/// number.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    /// A word, or multi-line text such as letters drawn in a grid. Lines are
+    /// stripped of trailing whitespace and blank lines around the text are
+    /// dropped.
+    Text(String),
+}
+
+impl Answer {
+    /// Reads an answer as it was printed or recorded. Canonical integers
+    /// become numbers; anything else, such as `007`, stays text.
+    pub fn parse(s: &str) -> Self {
+        let text = normalise(s);
+
+        if let Ok(n) = text.parse::<u128>() {
+            if n.to_string() == text {
+                return Answer::Unsigned(n);
+            }
+        }
+        if let Ok(n) = text.parse::<i128>() {
+            if n.to_string() == text {
+                return Answer::Signed(n);
+            }
+        }
+
+        Answer::Text(text)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Answer::Text(text) if text.is_empty())
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Text(text) if text.contains('\n'))
+    }
+
+    /// The answer with letters drawn over several lines read as text, as the
+    /// site expects them. Other answers, and drawings that are not letters,
+    /// are returned as they are.
+    pub fn letters(&self) -> Answer {
+        match self {
+            Answer::Text(text) if self.is_multiline() => {
+                ocr::recognise(text).map_or_else(|_| self.clone(), Answer::Text)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Whether the answers are equal once drawn letters are read, so a
+    /// drawing matches the letters recorded for it.
+    pub fn matches(&self, other: &Answer) -> bool {
+        self == other || self.letters() == other.letters()
+    }
+
+    /// The answer on a single line, for line based files. Newlines and
+    /// backslashes are escaped.
+    pub fn encode(&self) -> String {
+        match self {
+            Answer::Text(text) => text.replace('\\', "\\\\").replace('\n', "\\n"),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Reverses [`encode`](Self::encode).
+    pub fn decode(s: &str) -> Self {
+        let mut text = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match (c, chars.clone().next()) {
+                ('\\', Some('n')) => {
+                    text.push('\n');
+                    chars.next();
+                }
+                ('\\', Some('\\')) => {
+                    text.push('\\');
+                    chars.next();
+                }
+                _ => text.push(c),
+            }
+        }
+
+        Self::parse(&text)
+    }
+}
+
+fn normalise(s: &str) -> String {
+    let lines: Vec<&str> = s.lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+
+    match (start, end) {
+        (Some(start), Some(end)) if start == end => lines[start].trim_start().to_string(),
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Unsigned(b)) | (Answer::Unsigned(b), Answer::Signed(a)) => {
+                u128::try_from(*a).is_ok_and(|a| a == *b)
+            }
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => f.pad(&n.to_string()),
+            Answer::Unsigned(n) => f.pad(&n.to_string()),
+            Answer::Text(text) => f.pad(text),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident: $($ty:ty),+) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Self {
+                    Answer::$variant(n as _)
+                }
+            }
+        )+
+    };
+}
+
+from_integer!(Signed: i8, i16, i32, i64, i128, isize);
+from_integer!(Unsigned: u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::parse(&text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::parse(text)
+    }
+}
+
+impl From<char> for Answer {
+    fn from(c: char) -> Self {
+        Self::parse(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn integers_compare_by_value() {
+        assert_eq!(Answer::from(42u32), Answer::from(42i64));
+        assert_eq!(Answer::from(42usize), Answer::from(42u128));
+        assert_ne!(Answer::from(-1i32), Answer::from(u128::MAX));
+        assert_eq!(Answer::from(42u8), Answer::from("42"));
+        assert_ne!(Answer::from(42u8), Answer::from("042"));
+    }
+
+    #[test]
+    fn chars_parse_like_text() {
+        assert_eq!(Answer::from('5'), Answer::from("5"));
+        assert_eq!(Answer::from('5'), Answer::from(5u8));
+        assert_eq!(Answer::from('A'), Answer::from("A"));
+        assert_eq!(Answer::from(' '), Answer::from(""));
+    }
+
+    #[test]
+    fn parse_printed_answers() {
+        assert_eq!(Answer::parse(" 18\n"), Answer::Unsigned(18));
+        assert_eq!(Answer::parse("-7"), Answer::Signed(-7));
+        assert_eq!(
+            Answer::parse("340282366920938463463374607431768211455"),
+            Answer::Unsigned(u128::MAX)
+        );
+        assert_eq!(Answer::parse("007"), Answer::Text("007".to_string()));
+        assert_eq!(Answer::parse("+7"), Answer::Text("+7".to_string()));
+        assert_eq!(Answer::parse("ABC"), Answer::from("ABC"));
+    }
+
+    #[test]
+    fn normalise_text() {
+        let answer = Answer::from("\n\n#..#  \r\n####\n#..#\n\n");
+
+        assert_eq!(answer, Answer::Text("#..#\n####\n#..#".to_string()));
+        assert!(answer.is_multiline());
+        assert_eq!(Answer::from("  word \n"), Answer::from("word"));
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let answer = Answer::from("#.\\\n.#");
+
+        assert_eq!(answer.encode(), "#.\\\\\\n.#");
+        assert_eq!(Answer::decode(&answer.encode()), answer);
+        assert_eq!(Answer::decode("12"), Answer::Unsigned(12));
+    }
+
+    #[test]
+    fn read_drawn_letters() {
+        let drawing = Answer::from("#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###");
+
+        assert_eq!(drawing.letters(), Answer::from("HI"));
+        assert!(drawing.matches(&Answer::from("HI")));
+        assert!(!drawing.matches(&Answer::from("HJ")));
+        assert_eq!(Answer::from("#\n#").letters(), Answer::from("#\n#"));
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+};
+
+use crate::Answer;
+
+/// Accepted answers are kept in this file at the root of the repository so
+/// they are versioned alongside the solutions.
+pub const ANSWERS_PATH: &str = "answers.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+}
+
+/// Recorded answers, one per line in the form `year day part answer`.
+/// Multi-line answers are stored on one line with their newlines escaped.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<Key, Answer>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Correct,
+    Incorrect { expected: Answer },
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut entries = BTreeMap::new();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, ' ');
+            let mut next_num = |name: &str| {
+                fields
+                    .next()
+                    .and_then(|field| field.parse::<u32>().ok())
+                    .ok_or(format!("line {}: invalid {name}", line_no + 1))
+            };
+            let key = Key {
+                year: next_num("year")?,
+                day: next_num("day")?,
+                part: next_num("part")?,
+            };
+
+            let Some(answer) = fields.next().filter(|answer| !answer.is_empty()) else {
+                return Err(format!("line {}: missing answer", line_no + 1));
+            };
+
+            entries.insert(key, Answer::decode(answer));
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, key: Key) -> Option<&Answer> {
+        self.entries.get(&key)
+    }
+
+    /// Records the answer, unless it is empty and so could not be read back.
+    pub fn insert(&mut self, key: Key, answer: impl Into<Answer>) -> Result<(), String> {
+        let answer = answer.into();
+        if answer.is_empty() {
+            return Err("the answer is empty".to_string());
+        }
+
+        self.entries.insert(key, answer);
+
+        Ok(())
+    }
+
+    pub fn check(&self, key: Key, answer: &Answer) -> Status {
+        match self.get(key) {
+            Some(expected) if expected.matches(answer) => Status::Correct,
+            Some(expected) => Status::Incorrect {
+                expected: expected.clone(),
+            },
+            None => Status::Unknown,
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part answer")?;
+        for (key, answer) in &self.entries {
+            writeln!(
+                f,
+                "{} {} {} {}",
+                key.year,
+                key.day,
+                key.part,
+                answer.encode()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Correct => f.pad("ok"),
+            Status::Incorrect { expected } => {
+                f.pad(&format!("MISMATCH (expected {})", expected.encode()))
+            }
+            Status::Unknown => f.pad("unverified"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answers, Key, Status};
+    use crate::Answer;
+
+    const KEY: Key = Key {
+        year: 2024,
+        day: 4,
+        part: 1,
+    };
+
+    #[test]
+    fn reject_empty_answer() {
+        let mut answers = Answers::default();
+
+        assert!(answers.insert(KEY, ' ').is_err());
+        assert_eq!(answers.get(KEY), None);
+    }
+
+    #[test]
+    fn parse_and_print_round_trip() {
+        let content = "# year day part answer\n2024 4 1 18\n2024 4 2 9\n";
+        let answers = Answers::parse(content).unwrap();
+
+        assert_eq!(answers.get(KEY), Some(&Answer::Unsigned(18)));
+        assert_eq!(answers.to_string(), content);
+    }
+
+    #[test]
+    fn multiline_round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(KEY, "#..#\n####").unwrap();
+
+        let content = answers.to_string();
+        assert_eq!(content, "# year day part answer\n2024 4 1 #..#\\n####\n");
+        assert_eq!(Answers::parse(&content).unwrap(), answers);
+    }
+
+    #[test]
+    fn reject_missing_answer() {
+        assert!(Answers::parse("2024 4 1").is_err());
+        assert!(Answers::parse("2024 four 1 18").is_err());
+    }
+
+    #[test]
+    fn check_against_recorded() {
+        let mut answers = Answers::default();
+        answers.insert(KEY, "18").unwrap();
+
+        assert_eq!(answers.check(KEY, &Answer::from(18usize)), Status::Correct);
+        assert_eq!(
+            answers.check(KEY, &Answer::from(17usize)),
+            Status::Incorrect {
+                expected: Answer::from(18u64)
+            }
+        );
+        assert_eq!(
+            answers.check(Key { part: 2, ..KEY }, &Answer::from(9)),
+            Status::Unknown
+        );
+    }
+}
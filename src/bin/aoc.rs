@@ -0,0 +1,780 @@
+use std::{
+    env, fs, io, process,
+    time::{Duration, Instant},
+};
+
+use advent_of_code::{
+    alloc,
+    answers::{Answers, Key, ANSWERS_PATH},
+    dashboard, inputs, isolate, json,
+    perf::{self, Verdict, HISTORY_PATH},
+    report::{self, REPORT_PATH},
+    runner::{self, Format, Options, PartReport, Report},
+    scaffold,
+    submit::{self, Outcome},
+    watch, Answer, Puzzle, DAYS, LATEST_YEAR, YEARS,
+};
+
+const USAGE: &str = "usage: aoc [--year YEAR] <command> [options]
+
+commands:
+  run <day> [options]       solve a day and print its answers and timings
+      --submit              with --part, submit the answer and record it when correct
+      -y, --yes             submit without asking for confirmation
+  compare <day> [options]   run every implementation of a day's parts, check that
+                            they agree and show their timings side by side
+  record <day> [--part N]   store a day's computed answers as accepted
+  all [options]             solve every day on its cached input and summarise
+  watch <day> [--example]   re-run a day and its tests whenever its code or input changes
+  new <day>                 create and register a new day from the template, and its
+                            year when it is the first
+  verify                    re-run every day and compare against the recorded answers
+  validate <day> [options]  solve every cached input of a day, for every account,
+                            and check each against that account's answers
+  perf record [options]     time every day and append the results to the history
+  perf compare [options]    time every day and flag slowdowns against the history
+      --baseline COMMIT     compare against this commit instead of the latest entry
+      --threshold PCT       ignore changes smaller than this (default 5)
+  dashboard                 browse, run, test and bench every day in a full-screen view
+  report [options]          write an HTML report of every day to report.html
+      --output PATH         write it here instead
+      --mask                hide the answers
+
+options:
+  --year YEAR               the year of the puzzles (default the latest)
+  -r, --repeat N            run each phase N times and report min/median/mean
+  -f, --format FORMAT       output as text (default), json or ndjson
+  -e, --example             solve the example input instead of the real one
+  -t, --timeout SECS        kill a part that runs longer than this
+  -m, --memory MB           limit the memory each part may use (unix only)
+  -p, --part N              solve only part N
+  --force                   solve again instead of reusing cached answers
+
+Errors and warnings are logged to stderr. Set AOC_LOG for more or less, e.g.
+AOC_LOG=info, AOC_LOG=day05=debug or AOC_LOG=off";
+
+enum Command {
+    Run {
+        day: u32,
+        options: Options,
+        submit: Option<Submit>,
+    },
+    Compare {
+        day: u32,
+        options: Options,
+    },
+    Record {
+        day: u32,
+        part: Option<u32>,
+    },
+    All {
+        options: Options,
+    },
+    Watch {
+        day: u32,
+        example: bool,
+    },
+    New {
+        day: u32,
+    },
+    Verify,
+    Validate {
+        day: u32,
+        options: Options,
+    },
+    PerfRecord {
+        options: Options,
+    },
+    PerfCompare {
+        baseline: Option<String>,
+        threshold: f64,
+        options: Options,
+    },
+    Dashboard,
+    Report {
+        output: String,
+        mask: bool,
+        options: Options,
+    },
+}
+
+struct Submit {
+    /// Ask before sending the answer.
+    confirm: bool,
+}
+
+/// Runs per phase when timing for the history, unless `--repeat` is given.
+const PERF_REPEAT: &str = "20";
+
+impl Command {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let command = args.next().ok_or("missing command")?;
+
+        match command.as_str() {
+            "run" => {
+                let day = parse_day(args.next())?;
+
+                let (mut submit, mut confirm) = (false, true);
+                let mut rest = Vec::new();
+                for arg in args {
+                    match arg.as_str() {
+                        "--submit" => submit = true,
+                        "-y" | "--yes" => confirm = false,
+                        _ => rest.push(arg),
+                    }
+                }
+                let options = Options::from_args(rest.into_iter())?;
+
+                let submit = match (submit, options.part, options.example) {
+                    (false, _, _) => None,
+                    (true, None, _) => return Err("--submit needs --part".to_string()),
+                    (true, _, true) => {
+                        return Err("cannot submit the answer to an example".to_string())
+                    }
+                    (true, Some(_), false) => Some(Submit { confirm }),
+                };
+
+                Ok(Command::Run {
+                    day,
+                    options,
+                    submit,
+                })
+            }
+            "compare" => {
+                let day = parse_day(args.next())?;
+                let options = Options::from_args(args)?;
+                if options.format != Format::Text || options.isolated() {
+                    return Err(
+                        "compare does not support --format, --timeout or --memory".to_string()
+                    );
+                }
+
+                Ok(Command::Compare { day, options })
+            }
+            "record" => {
+                let day = parse_day(args.next())?;
+                let part = match (args.next().as_deref(), args.next()) {
+                    (None, _) => None,
+                    (Some("-p" | "--part"), Some(part)) => match part.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err(format!("invalid part: {part}")),
+                    },
+                    (Some(arg), _) => return Err(format!("unexpected argument: {arg}")),
+                };
+
+                Ok(Command::Record { day, part })
+            }
+            "all" => Ok(Command::All {
+                options: Options::from_args(args)?,
+            }),
+            "watch" => {
+                let day = parse_day(args.next())?;
+                let example = match args.next().as_deref() {
+                    None => false,
+                    Some("-e" | "--example") => true,
+                    Some(arg) => return Err(format!("unexpected argument: {arg}")),
+                };
+
+                Ok(Command::Watch { day, example })
+            }
+            "new" => Ok(Command::New {
+                day: parse_day(args.next())?,
+            }),
+            "verify" => Ok(Command::Verify),
+            "dashboard" => Ok(Command::Dashboard),
+            "report" => {
+                let mut output = REPORT_PATH.to_string();
+                let mut mask = false;
+                let mut rest = Vec::new();
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--output" => output = args.next().ok_or(format!("{arg} needs a value"))?,
+                        "--mask" => mask = true,
+                        _ => rest.push(arg),
+                    }
+                }
+                let options = Options::from_args(rest.into_iter())?;
+                if options.format != Format::Text {
+                    return Err("report does not support --format".to_string());
+                }
+
+                Ok(Command::Report {
+                    output,
+                    mask,
+                    options,
+                })
+            }
+            "validate" => {
+                let day = parse_day(args.next())?;
+                let options = Options::from_args(args)?;
+                if options.example {
+                    return Err("validate only runs the cached inputs".to_string());
+                }
+
+                Ok(Command::Validate { day, options })
+            }
+            "perf" => {
+                let subcommand = args.next().ok_or("missing perf command")?;
+
+                let mut baseline = None;
+                let mut threshold = perf::DEFAULT_THRESHOLD;
+                let mut rest = vec!["--repeat".to_string(), PERF_REPEAT.to_string()];
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--baseline" => {
+                            baseline = Some(args.next().ok_or(format!("{arg} needs a value"))?);
+                        }
+                        "--threshold" => {
+                            let value = args.next().ok_or(format!("{arg} needs a value"))?;
+                            threshold = value
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|threshold| *threshold >= 0.0)
+                                .ok_or(format!("invalid threshold: {value}"))?;
+                        }
+                        _ => rest.push(arg),
+                    }
+                }
+                let options = Options::from_args(rest.into_iter())?;
+
+                match subcommand.as_str() {
+                    "record" if baseline.is_none() => Ok(Command::PerfRecord { options }),
+                    "record" => Err("--baseline only applies to perf compare".to_string()),
+                    "compare" => Ok(Command::PerfCompare {
+                        baseline,
+                        threshold,
+                        options,
+                    }),
+                    _ => Err(format!("unknown perf command: {subcommand}")),
+                }
+            }
+            _ => Err(format!("unknown command: {command}")),
+        }
+    }
+}
+
+fn parse_day(arg: Option<String>) -> Result<u32, String> {
+    let arg = arg.ok_or("missing day")?;
+
+    arg.parse()
+        .ok()
+        .filter(|day| (1..=DAYS).contains(day))
+        .ok_or(format!("invalid day: {arg}, expected 1 to {DAYS}"))
+}
+
+/// Takes `--year YEAR` out of the arguments, wherever it is.
+fn extract_year(args: &mut Vec<String>) -> Result<u32, String> {
+    let Some(i) = args.iter().position(|arg| arg == "--year") else {
+        return Ok(LATEST_YEAR);
+    };
+
+    args.remove(i);
+    if i == args.len() {
+        return Err("--year needs a value".to_string());
+    }
+    let year = args.remove(i);
+
+    year.parse().map_err(|_| format!("invalid year: {year}"))
+}
+
+fn main() {
+    isolate::serve(YEARS.iter().flat_map(|(_, puzzles)| *puzzles));
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let parsed =
+        extract_year(&mut args).and_then(|year| Ok((year, Command::from_args(args.into_iter())?)));
+    let (year, command) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::Run {
+            day,
+            options,
+            submit,
+        } => run(year, day, &options, submit.as_ref()),
+        Command::Compare { day, options } => compare(year, day, &options),
+        Command::Record { day, part } => record(year, day, part),
+        Command::All { options } => all(year, &options),
+        Command::Watch { day, example } => watch::watch(year, day, example),
+        Command::New { day } => scaffold::new_day(year, day),
+        Command::Verify => verify(year),
+        Command::Validate { day, options } => validate(year, day, &options),
+        Command::PerfRecord { options } => perf_record(year, &options),
+        Command::PerfCompare {
+            baseline,
+            threshold,
+            options,
+        } => perf_compare(year, baseline.as_deref(), threshold, &options),
+        Command::Dashboard => puzzles(year).and_then(|puzzles| dashboard::run(year, puzzles)),
+        Command::Report {
+            output,
+            mask,
+            options,
+        } => write_report(year, &output, mask, &options),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+fn puzzles(year: u32) -> Result<&'static [Puzzle], String> {
+    advent_of_code::puzzles(year).ok_or(format!("{year} has no puzzles"))
+}
+
+fn puzzle(year: u32, day: u32) -> Result<&'static Puzzle, String> {
+    advent_of_code::puzzle(year, day).ok_or(format!("day {day} of {year} is not registered"))
+}
+
+fn load_answers() -> Result<Answers, String> {
+    Answers::load(ANSWERS_PATH).map_err(|e| format!("Could not load {ANSWERS_PATH}: {e}"))
+}
+
+fn key(year: u32, day: u32, part: u32) -> Key {
+    Key { year, day, part }
+}
+
+fn run(year: u32, day: u32, options: &Options, submit: Option<&Submit>) -> Result<(), String> {
+    let puzzle = puzzle(year, day)?;
+    let answers = load_answers()?;
+
+    let report = puzzle.run(options);
+    runner::print_report(&report, &answers, options);
+
+    let failed = report
+        .parts
+        .iter()
+        .filter(|part| part.answer.is_err())
+        .count();
+    if failed > 0 {
+        return Err(format!("{failed} part(s) failed"));
+    }
+
+    match (submit, report.parts.first()) {
+        (
+            Some(submit),
+            Some(PartReport {
+                part,
+                answer: Ok(answer),
+                ..
+            }),
+        ) => submit_answer(year, day, *part, answer, submit, answers),
+        _ => Ok(()),
+    }
+}
+
+fn submit_answer(
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &Answer,
+    submit: &Submit,
+    mut answers: Answers,
+) -> Result<(), String> {
+    if answer.is_empty() {
+        return Err(format!(
+            "day {day} part {part} has an empty answer, not submitting"
+        ));
+    }
+    if let Some(recorded) = answers.get(key(year, day, part)) {
+        return Err(format!(
+            "day {day} part {part} already has the recorded answer {recorded}, not submitting"
+        ));
+    }
+
+    if submit.confirm {
+        eprint!("Submit {answer} for day {day} part {part}? [y/N] ");
+        let mut reply = String::new();
+        io::stdin()
+            .read_line(&mut reply)
+            .map_err(|e| format!("Could not read confirmation: {e}"))?;
+
+        if !matches!(reply.trim(), "y" | "Y" | "yes") {
+            eprintln!("Not submitted");
+            return Ok(());
+        }
+    }
+
+    let outcome = submit::submit(year, day, part, answer)?;
+    eprintln!("Day {day} part {part}: {outcome}");
+
+    match outcome {
+        Outcome::Correct => {
+            answers.insert(key(year, day, part), answer.letters())?;
+            answers
+                .save(ANSWERS_PATH)
+                .map_err(|e| format!("Could not save {ANSWERS_PATH}: {e}"))?;
+            eprintln!("Recorded day {day} part {part}: {answer}");
+
+            Ok(())
+        }
+        _ => Err(format!("{answer} was not accepted")),
+    }
+}
+
+fn compare(year: u32, day: u32, options: &Options) -> Result<(), String> {
+    let puzzle = puzzle(year, day)?;
+    let reports = puzzle.compare(options);
+
+    println!(
+        "{:>4}  {:<16}  {:>16}  {:>10}  {:>8}  status",
+        "part", "implementation", "answer", "time", "relative"
+    );
+
+    let mut problems = 0;
+    for &part in options.parts() {
+        let reports: Vec<_> = reports
+            .iter()
+            .filter(|report| report.part == part)
+            .collect();
+        // Checked against the default implementation unless it failed.
+        let reference = reports.iter().find(|report| report.answer.is_ok());
+
+        for report in &reports {
+            let status = match (&report.answer, reference) {
+                (Err(failure), _) => failure.to_string(),
+                (Ok(answer), Some(reference)) if reference.answer.as_ref() != Ok(answer) => {
+                    "DISAGREES".to_string()
+                }
+                (Ok(_), _) => "agrees".to_string(),
+            };
+            if status != "agrees" {
+                problems += 1;
+            }
+
+            let relative = reference.map_or("-".to_string(), |reference| {
+                format!(
+                    "{:.2}x",
+                    report.time.median.as_secs_f64() / reference.time.median.as_secs_f64()
+                )
+            });
+
+            println!(
+                "{:>4}  {:<16}  {:>16}  {:>10.2?}  {relative:>8}  {status}",
+                part,
+                report.name,
+                report
+                    .answer
+                    .as_ref()
+                    .map_or("-".to_string(), Answer::encode),
+                report.time.median
+            );
+        }
+    }
+
+    if problems > 0 {
+        Err(format!("{problems} implementation(s) failed or disagree"))
+    } else {
+        Ok(())
+    }
+}
+
+fn record(year: u32, day: u32, part: Option<u32>) -> Result<(), String> {
+    let puzzle = puzzle(year, day)?;
+    let mut answers = load_answers()?;
+
+    // Solve again, as a cached answer would only be recorded back.
+    let report = puzzle.run(&uncached());
+
+    for solved in &report.parts {
+        let p = solved.part;
+        if part.is_some_and(|part| part != p) {
+            continue;
+        }
+
+        match &solved.answer {
+            Ok(answer) => match answers.insert(key(year, day, p), answer.letters()) {
+                Ok(()) => println!("Recorded day {day} part {p}: {answer}"),
+                Err(e) => eprintln!("Not recording day {day} part {p}: {e}"),
+            },
+            Err(failure) => eprintln!("Not recording day {day} part {p}: {failure}"),
+        }
+    }
+
+    answers
+        .save(ANSWERS_PATH)
+        .map_err(|e| format!("Could not save {ANSWERS_PATH}: {e}"))
+}
+
+fn all(year: u32, options: &Options) -> Result<(), String> {
+    let puzzles = puzzles(year)?;
+    let answers = load_answers()?;
+
+    let start = Instant::now();
+    let results = runner::run_all(puzzles, options);
+    let wall = start.elapsed();
+
+    if options.format != Format::Text {
+        return all_json(year, &results, &answers, options.format);
+    }
+
+    print!(
+        "{:>3}  {:>4}  {:>16}  {:<10}  {:>10}",
+        "day", "part", "answer", "status", "time"
+    );
+    if alloc::enabled() {
+        print!("  {:>10}  {:>10}", "allocs", "peak");
+    }
+    println!();
+
+    let mut total = Duration::ZERO;
+    let mut failures = 0;
+    for (day, result) in &results {
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                failures += 1;
+                println!("{day:>3}  {:>4}  {:>16}  {e}", "-", "-");
+                continue;
+            }
+        };
+
+        total += report.load + report.parse.as_ref().map_or(Duration::ZERO, |p| p.median);
+
+        for part in &report.parts {
+            if part.failed(year, *day, &answers) {
+                failures += 1;
+            }
+
+            total += part.time.median;
+            print!(
+                "{day:>3}  {:>4}  {:>16}  {:<10}  {:>10.2?}",
+                part.part,
+                part.answer.as_ref().map_or("-".to_string(), Answer::encode),
+                part.status(year, *day, &answers),
+                part.time.median
+            );
+            if let Some(usage) = &part.alloc {
+                print!(
+                    "  {:>10}  {:>10}",
+                    usage.allocations,
+                    alloc::format_bytes(usage.peak)
+                );
+            }
+            println!();
+        }
+    }
+
+    println!("total {total:.2?} (wall clock {wall:.2?})");
+    let cached = results
+        .iter()
+        .filter(|(_, result)| result.as_ref().is_ok_and(|report| report.cached))
+        .count();
+    if cached > 0 {
+        println!("{cached} day(s) reused cached answers and timings, --force to solve again");
+    }
+
+    if failures > 0 {
+        Err(format!("{failures} part(s) failed or did not match"))
+    } else {
+        Ok(())
+    }
+}
+
+fn all_json(
+    year: u32,
+    results: &[(u32, Result<Report, String>)],
+    answers: &Answers,
+    format: Format,
+) -> Result<(), String> {
+    let mut records = Vec::new();
+    for (day, result) in results {
+        match result {
+            Ok(report) => records.extend(json::report_records(report, answers)),
+            Err(e) => records.push(json::error_record(year, *day, e)),
+        }
+    }
+
+    json::print(&records, format);
+
+    let failures = records
+        .iter()
+        .filter(|record| record["status"] != "correct" && record["status"] != "unknown")
+        .count();
+    if failures > 0 {
+        Err(format!("{failures} part(s) failed or did not match"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Options that solve every day again, for commands that check or record
+/// answers and so must not trust the cache.
+fn uncached() -> Options {
+    Options {
+        force: true,
+        ..Options::default()
+    }
+}
+
+fn verify(year: u32) -> Result<(), String> {
+    let puzzles = puzzles(year)?;
+    let answers = load_answers()?;
+
+    let mut mismatches = 0;
+    for puzzle in puzzles {
+        let report = puzzle.run(&uncached());
+
+        for part in &report.parts {
+            if part.failed(year, puzzle.day, &answers) {
+                mismatches += 1;
+            }
+
+            println!(
+                "day {:>2} part {}  {:>16}  {}",
+                puzzle.day,
+                part.part,
+                part.answer.as_ref().map_or("-".to_string(), Answer::encode),
+                part.status(year, puzzle.day, &answers)
+            );
+        }
+    }
+
+    if mismatches > 0 {
+        Err(format!(
+            "{mismatches} answer(s) do not match {ANSWERS_PATH}"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn write_report(year: u32, output: &str, mask: bool, options: &Options) -> Result<(), String> {
+    let puzzles = puzzles(year)?;
+    let answers = load_answers()?;
+
+    let results = runner::run_all(puzzles, options);
+    let visualisations = report::visualise(puzzles);
+    let html = report::render(year, &results, &answers, &visualisations, mask);
+
+    fs::write(output, html).map_err(|e| format!("Could not write {output}: {e}"))?;
+    println!("Wrote {output}");
+
+    Ok(())
+}
+
+fn validate(year: u32, day: u32, options: &Options) -> Result<(), String> {
+    let puzzle = puzzle(year, day)?;
+    let sources = inputs::sources(year, day);
+    if sources.is_empty() {
+        return Err(format!("no cached inputs for day {day}"));
+    }
+
+    println!(
+        "{:<12}  {:<20}  {:>4}  {:>16}  status",
+        "account", "input", "part", "answer"
+    );
+
+    let mut disagreements = 0;
+    for source in &sources {
+        let answers = source.load_answers()?;
+        let input = fs::read_to_string(&source.path)
+            .map_err(|e| format!("Could not read {}: {e}", source.path.display()))?;
+
+        let report = puzzle.solve(&input, options);
+        for part in &report.parts {
+            if part.failed(year, day, &answers) {
+                disagreements += 1;
+            }
+
+            println!(
+                "{:<12}  {:<20}  {:>4}  {:>16}  {}",
+                source.account_name(),
+                source.path.display(),
+                part.part,
+                part.answer.as_ref().map_or("-".to_string(), Answer::encode),
+                part.status(year, day, &answers)
+            );
+        }
+    }
+
+    if disagreements > 0 {
+        Err(format!(
+            "{disagreements} result(s) failed or disagree with the recorded answers"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn perf_record(year: u32, options: &Options) -> Result<(), String> {
+    let entry = perf::Entry::current(perf::measure(puzzles(year)?, options));
+    if entry.results.is_empty() {
+        return Err("nothing to record".to_string());
+    }
+
+    println!(
+        "{:>3}  {:<6}  {:>10}  {:>10}",
+        "day", "phase", "mean", "std dev"
+    );
+    for measurement in &entry.results {
+        println!(
+            "{:>3}  {:<6}  {:>10.2?}  {:>10.2?}",
+            measurement.day, measurement.phase, measurement.time.mean, measurement.time.std_dev
+        );
+    }
+
+    perf::append(HISTORY_PATH, &entry)
+        .map_err(|e| format!("Could not write {HISTORY_PATH}: {e}"))?;
+    println!(
+        "Recorded {} on {} in {HISTORY_PATH}",
+        entry.revision(),
+        entry.machine
+    );
+
+    Ok(())
+}
+
+fn perf_compare(
+    year: u32,
+    baseline: Option<&str>,
+    threshold: f64,
+    options: &Options,
+) -> Result<(), String> {
+    let history =
+        perf::load(HISTORY_PATH).map_err(|e| format!("Could not load {HISTORY_PATH}: {e}"))?;
+    let machine = perf::machine();
+    let baseline = perf::baseline(&history, year, &machine, baseline).ok_or(format!(
+        "no matching baseline for {year} on {machine} in {HISTORY_PATH}, run `aoc perf record --year {year}` first"
+    ))?;
+
+    let current = perf::measure(puzzles(year)?, options);
+    let comparisons = perf::compare(&baseline.results, &current, threshold);
+
+    println!("baseline {} on {machine}", baseline.revision());
+    println!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>8}  verdict",
+        "day", "phase", "baseline", "current", "change"
+    );
+    for comparison in &comparisons {
+        let baseline = comparison
+            .baseline
+            .map_or("-".to_string(), |time| format!("{time:.2?}"));
+        let change = comparison
+            .change
+            .map_or("-".to_string(), |change| format!("{change:+.1}%"));
+
+        println!(
+            "{:>3}  {:<6}  {baseline:>10}  {:>10.2?}  {change:>8}  {}",
+            comparison.day, comparison.phase, comparison.current, comparison.verdict
+        );
+    }
+
+    let slower = comparisons
+        .iter()
+        .filter(|comparison| comparison.verdict == Verdict::Slower)
+        .count();
+    if slower > 0 {
+        Err(format!("{slower} phase(s) are significantly slower"))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+use advent_of_code::{runner, y2024::day01::Day1};
+
+fn main() {
+    runner::main::<Day1>();
+}
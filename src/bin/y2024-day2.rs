@@ -0,0 +1,5 @@
+use advent_of_code::{runner, y2024::day02::Day2};
+
+fn main() {
+    runner::main::<Day2>();
+}
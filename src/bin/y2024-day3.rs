@@ -0,0 +1,5 @@
+use advent_of_code::{runner, y2024::day03::Day3};
+
+fn main() {
+    runner::main::<Day3>();
+}
@@ -0,0 +1,5 @@
+use advent_of_code::{runner, y2024::day04::Day4};
+
+fn main() {
+    runner::main::<Day4>();
+}
@@ -0,0 +1,5 @@
+use advent_of_code::{runner, y2024::day05::Day5};
+
+fn main() {
+    runner::main::<Day5>();
+}
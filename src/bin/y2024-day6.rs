@@ -0,0 +1,5 @@
+use advent_of_code::{runner, y2024::day06::Day6};
+
+fn main() {
+    runner::main::<Day6>();
+}
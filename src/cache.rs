@@ -0,0 +1,403 @@
+//! Answers remembered between runs, so that slow days are not solved again
+//! when neither their input nor the code changed. Each part is keyed by the
+//! day and a hash of its input, and stored with a fingerprint of the build
+//! that solved it. Changing the code, the profile or the features changes the
+//! fingerprint, which makes every entry stale until it is solved again.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    runner::{Options, PartReport, Report, Stats},
+    warn, Answer, Puzzle,
+};
+
+/// Kept with the inputs, as it holds answers derived from them.
+pub const CACHE_PATH: &str = "input/cache.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Key {
+    year: u32,
+    day: u32,
+    part: u32,
+    input: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    build: u64,
+    parse: Duration,
+    time: Duration,
+    answer: Answer,
+}
+
+/// Cached answers, one per line in the form
+/// `year day part input build parse_ns time_ns answer` with the hashes in hex.
+#[derive(Debug, PartialEq)]
+pub struct Cache {
+    /// The fingerprint of this build, the only one whose entries are used.
+    build: u64,
+    entries: BTreeMap<Key, Entry>,
+    /// The entries inserted since loading, which saving merges into the file.
+    added: BTreeSet<Key>,
+}
+
+impl Cache {
+    pub fn new(build: u64) -> Self {
+        Self {
+            build,
+            entries: BTreeMap::new(),
+            added: BTreeSet::new(),
+        }
+    }
+
+    /// Loads the cache for this build. A missing file is an empty cache, and
+    /// lines that cannot be read are dropped as the cache can always be
+    /// rebuilt.
+    pub fn load(path: impl AsRef<Path>, build: u64) -> io::Result<Self> {
+        let mut cache = Self::new(build);
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(cache),
+            Err(e) => return Err(e),
+        };
+
+        cache.entries = content.lines().filter_map(parse_line).collect();
+
+        Ok(cache)
+    }
+
+    /// Merges the entries inserted since loading into the file, keeping those
+    /// that other processes saved in the meantime. The file is locked while it
+    /// is read and rewritten.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let _lock = Lock::acquire(path)?;
+        let mut merged = Self::load(path, self.build)?;
+        for key in &self.added {
+            merged.entries.insert(*key, self.entries[key].clone());
+        }
+
+        // Written aside and renamed, so that readers never see half a file.
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, merged.to_string())?;
+        fs::rename(temp, path)
+    }
+
+    /// A report of the parts solved by this build for this input, if every
+    /// one of them is cached.
+    pub fn get(&self, year: u32, day: u32, parts: &[u32], input: &str) -> Option<Report> {
+        let input = hash(input.as_bytes());
+        let entries = parts
+            .iter()
+            .map(|&part| {
+                let key = Key {
+                    year,
+                    day,
+                    part,
+                    input,
+                };
+
+                self.entries
+                    .get(&key)
+                    .filter(|entry| entry.build == self.build)
+                    .map(|entry| (part, entry))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Report {
+            year,
+            day,
+            load: Duration::ZERO,
+            parse: entries
+                .first()
+                .map(|(_, entry)| Stats::from_samples(vec![entry.parse])),
+            parse_alloc: None,
+            parts: entries
+                .into_iter()
+                .map(|(part, entry)| PartReport {
+                    part,
+                    answer: Ok(entry.answer.clone()),
+                    time: Stats::from_samples(vec![entry.time]),
+                    alloc: None,
+                })
+                .collect(),
+            cached: true,
+        })
+    }
+
+    /// Remembers the answers of the report's parts that succeeded, replacing
+    /// those of any earlier build for the same input.
+    pub fn insert(&mut self, report: &Report, input: &str) {
+        let Some(parse) = &report.parse else {
+            return;
+        };
+
+        let input = hash(input.as_bytes());
+        for part in &report.parts {
+            let Ok(answer) = &part.answer else {
+                continue;
+            };
+
+            let key = Key {
+                year: report.year,
+                day: report.day,
+                part: part.part,
+                input,
+            };
+            let entry = Entry {
+                build: self.build,
+                parse: parse.median,
+                time: part.time.median,
+                answer: answer.clone(),
+            };
+
+            self.entries.insert(key, entry);
+            self.added.insert(key);
+        }
+    }
+}
+
+impl Display for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part input build parse_ns time_ns answer")?;
+        for (key, entry) in &self.entries {
+            writeln!(
+                f,
+                "{} {} {} {:016x} {:016x} {} {} {}",
+                key.year,
+                key.day,
+                key.part,
+                key.input,
+                entry.build,
+                entry.parse.as_nanos(),
+                entry.time.as_nanos(),
+                entry.answer.encode()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Loads the cache for this build from [`CACHE_PATH`], starting afresh if
+/// it cannot be read.
+pub fn load() -> Cache {
+    Cache::load(CACHE_PATH, fingerprint()).unwrap_or_else(|e| {
+        warn!("Could not load {CACHE_PATH}: {e}");
+        Cache::new(fingerprint())
+    })
+}
+
+pub fn save(cache: &Cache) {
+    if let Err(e) = cache.save(CACHE_PATH) {
+        warn!("Could not save {CACHE_PATH}: {e}");
+    }
+}
+
+/// Solves the puzzle like [`Puzzle::solve`], but reuses the cached answers
+/// when the options allow it and caches new ones.
+pub fn solve(puzzle: &Puzzle, input: &str, options: &Options) -> Report {
+    if !options.cached() {
+        return puzzle.solve(input, options);
+    }
+
+    let mut cache = load();
+    if let Some(report) = cache.get(puzzle.year, puzzle.day, options.parts(), input) {
+        return report;
+    }
+
+    let report = puzzle.solve(input, options);
+    cache.insert(&report, input);
+    save(&cache);
+
+    report
+}
+
+/// A lock file next to the cache, removed when dropped.
+struct Lock(PathBuf);
+
+impl Lock {
+    /// Waits this long for another process to save before giving up.
+    const TIMEOUT: Duration = Duration::from_secs(5);
+    /// A lock this old was left behind by a process that died while saving.
+    const STALE: Duration = Duration::from_secs(30);
+
+    fn acquire(path: &Path) -> io::Result<Self> {
+        let lock = path.with_extension("lock");
+        let start = SystemTime::now();
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&lock) {
+                Ok(_) => return Ok(Self(lock)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+
+            let age = fs::metadata(&lock)
+                .and_then(|meta| meta.modified())
+                .map(|modified| modified.elapsed().unwrap_or_default());
+            if age.is_ok_and(|age| age > Self::STALE) {
+                let _ = fs::remove_file(&lock);
+                continue;
+            }
+            if start.elapsed().unwrap_or_default() > Self::TIMEOUT {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("{} is held by another process", lock.display()),
+                ));
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn parse_line(line: &str) -> Option<(Key, Entry)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut fields = line.splitn(8, ' ');
+    let mut next = || fields.next();
+    let key = Key {
+        year: next()?.parse().ok()?,
+        day: next()?.parse().ok()?,
+        part: next()?.parse().ok()?,
+        input: u64::from_str_radix(next()?, 16).ok()?,
+    };
+    let entry = Entry {
+        build: u64::from_str_radix(next()?, 16).ok()?,
+        parse: Duration::from_nanos(next()?.parse().ok()?),
+        time: Duration::from_nanos(next()?.parse().ok()?),
+        answer: Answer::decode(next().filter(|answer| !answer.is_empty())?),
+    };
+
+    Some((key, entry))
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher is the same in
+/// every build.
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Identifies the build by a hash of its sources, profile and features, taken
+/// by the build script, so that `aoc` and the day binaries built together
+/// share entries while a release build does not reuse debug timings.
+pub fn fingerprint() -> u64 {
+    u64::from_str_radix(env!("AOC_BUILD"), 16).expect("the build script hashes the sources")
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{
+        runner::{PartReport, Report, Stats},
+        Answer,
+    };
+
+    use super::{hash, Cache};
+
+    fn report(answers: &[(u32, &str)]) -> Report {
+        Report {
+            year: 2024,
+            day: 6,
+            load: Duration::ZERO,
+            parse: Some(Stats::from_samples(vec![Duration::from_micros(5)])),
+            parse_alloc: None,
+            parts: answers
+                .iter()
+                .map(|&(part, answer)| PartReport {
+                    part,
+                    answer: Ok(Answer::from(answer)),
+                    time: Stats::from_samples(vec![Duration::from_millis(300)]),
+                    alloc: None,
+                })
+                .collect(),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn reuse_for_same_input_and_build() {
+        let mut cache = Cache::new(1);
+        cache.insert(&report(&[(1, "41"), (2, "#.\n.#")]), "input");
+
+        let cached = cache.get(2024, 6, &[1, 2], "input").unwrap();
+
+        assert!(cached.cached);
+        assert_eq!(cached.parts[1].answer, Ok(Answer::from("#.\n.#")));
+        assert_eq!(cached.parts[0].time.median, Duration::from_millis(300));
+        assert!(cache.get(2024, 6, &[1, 2], "other input").is_none());
+        assert!(cache.get(2024, 5, &[1], "input").is_none());
+    }
+
+    #[test]
+    fn miss_when_a_part_is_missing() {
+        let mut cache = Cache::new(1);
+        cache.insert(&report(&[(1, "41")]), "input");
+
+        assert!(cache.get(2024, 6, &[1], "input").is_some());
+        assert!(cache.get(2024, 6, &[1, 2], "input").is_none());
+    }
+
+    #[test]
+    fn round_trip_and_ignore_other_builds() {
+        let mut cache = Cache::new(1);
+        cache.insert(&report(&[(1, "41"), (2, "6")]), "input");
+
+        let path = std::env::temp_dir().join(format!("aoc-cache-{}.txt", std::process::id()));
+        cache.save(&path).unwrap();
+        let same = Cache::load(&path, 1).unwrap();
+        let rebuilt = Cache::load(&path, 2).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(same.entries, cache.entries);
+        assert!(rebuilt.get(2024, 6, &[1], "input").is_none());
+    }
+
+    #[test]
+    fn merge_entries_saved_meanwhile() {
+        let path = std::env::temp_dir().join(format!("aoc-merge-{}.txt", std::process::id()));
+        let mut first = Cache::load(&path, 1).unwrap();
+        let mut second = Cache::load(&path, 1).unwrap();
+
+        first.insert(&report(&[(1, "41")]), "first input");
+        second.insert(&report(&[(1, "6")]), "second input");
+        first.save(&path).unwrap();
+        second.save(&path).unwrap();
+        let merged = Cache::load(&path, 1).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(merged.get(2024, 6, &[1], "first input").is_some());
+        assert!(merged.get(2024, 6, &[1], "second input").is_some());
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -0,0 +1,626 @@
+//! A full-screen terminal dashboard listing every day of the year with its
+//! stars, latest answers, verification status and timings. Days are run,
+//! tested against their examples and benchmarked in the background, and the
+//! logs they write are shown in a side pane.
+
+use std::{
+    env, fs, io,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Row, Table, TableState, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    answers::{Answers, Key, Status, ANSWERS_PATH},
+    cache::{self, Cache},
+    examples::{self, Checked},
+    get_cached_input, log,
+    runner::{self, Options, Report},
+    Puzzle, DAYS,
+};
+
+/// Runs per phase when benchmarking a day.
+pub const BENCH_REPEAT: usize = 20;
+
+/// How long to wait for a key before checking on the running days again.
+const TICK: Duration = Duration::from_millis(100);
+
+const MAX_LOG_LINES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Run,
+    Test,
+    Bench,
+}
+
+/// What a background run of a day sends back.
+pub enum Outcome {
+    Report(Report),
+    Tests(Result<Vec<Checked>, String>),
+    Error(String),
+}
+
+pub struct Day {
+    pub day: u32,
+    /// Missing for days that are not solved yet.
+    pub puzzle: Option<&'static Puzzle>,
+    pub report: Option<Report>,
+    pub tests: Option<Vec<Checked>>,
+    /// The action running in the background, if any.
+    pub busy: Option<Action>,
+}
+
+pub struct Dashboard {
+    pub year: u32,
+    pub days: Vec<Day>,
+    pub selected: usize,
+    /// Run on the example input instead of the cached one.
+    pub example: bool,
+    pub answers: Answers,
+    pub log: Vec<String>,
+    pub quit: bool,
+}
+
+/// Opens the dashboard for the given year's puzzles until `q` is pressed.
+pub fn run(year: u32, puzzles: &'static [Puzzle]) -> Result<(), String> {
+    let answers =
+        Answers::load(ANSWERS_PATH).map_err(|e| format!("Could not load {ANSWERS_PATH}: {e}"))?;
+
+    // The log pane has room for more than stderr would, so show info by
+    // default.
+    if env::var(log::ENV_VAR).is_err() {
+        log::init("info")?;
+    }
+    let (log_sink, logs) = mpsc::channel();
+    log::redirect(log_sink);
+
+    let mut dashboard = Dashboard::new(year, puzzles, answers);
+    dashboard.load_cached(&cache::load());
+    let mut terminal = ratatui::init();
+    let result = dashboard.event_loop(&mut terminal, &logs);
+    ratatui::restore();
+
+    result.map_err(|e| format!("Dashboard failed: {e}"))
+}
+
+impl Dashboard {
+    pub fn new(year: u32, puzzles: &'static [Puzzle], answers: Answers) -> Self {
+        let days = (1..=DAYS)
+            .map(|day| Day {
+                day,
+                puzzle: puzzles.iter().find(|puzzle| puzzle.day == day),
+                report: None,
+                tests: None,
+                busy: None,
+            })
+            .collect();
+
+        Self {
+            year,
+            days,
+            selected: 0,
+            example: false,
+            answers,
+            log: Vec::new(),
+            quit: false,
+        }
+    }
+
+    /// Shows the answers last cached for each day's input, so that the days
+    /// solved before do not start out blank.
+    pub fn load_cached(&mut self, cache: &Cache) {
+        for day in &mut self.days {
+            let Some(puzzle) = day.puzzle else {
+                continue;
+            };
+
+            if let Some(input) = get_cached_input(puzzle.year, puzzle.day) {
+                day.report = cache.get(puzzle.year, puzzle.day, &[1, 2], &input);
+            }
+        }
+    }
+
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        logs: &Receiver<String>,
+    ) -> io::Result<()> {
+        let (results, finished) = mpsc::channel();
+
+        while !self.quit {
+            for line in logs.try_iter() {
+                self.push_log(line);
+            }
+            for (day, outcome) in finished.try_iter() {
+                self.finish(day, outcome);
+            }
+
+            terminal.draw(|frame| self.render(frame))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        if let Some(action) = self.handle_key(key.code) {
+                            self.start(action, &results);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies a key press, returning the action to start on the selected
+    /// day, if any.
+    pub fn handle_key(&mut self, code: KeyCode) -> Option<Action> {
+        let action = match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.quit = true;
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.days.len() - 1);
+                None
+            }
+            KeyCode::Char('e') => {
+                self.example = !self.example;
+                None
+            }
+            KeyCode::Char('c') => {
+                self.log.clear();
+                None
+            }
+            KeyCode::Char('r') => Some(Action::Run),
+            KeyCode::Char('t') => Some(Action::Test),
+            KeyCode::Char('b') => Some(Action::Bench),
+            _ => None,
+        }?;
+
+        let day = &self.days[self.selected];
+        if day.puzzle.is_none() {
+            self.push_error(day.day, "not solved yet".to_string());
+            return None;
+        }
+        if day.busy.is_some() {
+            return None;
+        }
+
+        Some(action)
+    }
+
+    /// Starts the action on the selected day in the background.
+    fn start(&mut self, action: Action, results: &Sender<(u32, Outcome)>) {
+        let example = self.example;
+        let day = &mut self.days[self.selected];
+        let Some(puzzle) = day.puzzle else {
+            return;
+        };
+        day.busy = Some(action);
+
+        let day = day.day;
+        let results = results.clone();
+        thread::spawn(move || {
+            // Always report back, even after a panic, so the day is not left
+            // busy.
+            let outcome = runner::catch_panic(|| work(puzzle, action, example))
+                .unwrap_or_else(|failure| Outcome::Error(failure.to_string()));
+            let _ = results.send((day, outcome));
+        });
+    }
+
+    pub fn finish(&mut self, day: u32, outcome: Outcome) {
+        let Some(index) = self.days.iter().position(|d| d.day == day) else {
+            self.push_error(day, "finished but is not on the dashboard".to_string());
+            return;
+        };
+        self.days[index].busy = None;
+
+        match outcome {
+            Outcome::Report(report) => {
+                for part in &report.parts {
+                    if let Err(failure) = &part.answer {
+                        self.push_error(day, format!("part {}: {failure}", part.part));
+                    }
+                }
+                self.days[index].report = Some(report);
+            }
+            Outcome::Tests(Ok(checked)) => {
+                for failed in checked.iter().filter(|checked| !checked.passed()) {
+                    self.push_error(day, failed.to_string());
+                }
+                self.days[index].tests = Some(checked);
+            }
+            Outcome::Tests(Err(e)) | Outcome::Error(e) => self.push_error(day, e),
+        }
+    }
+
+    /// The number of parts of the day with a recorded answer.
+    pub fn stars(&self, day: u32) -> usize {
+        [1, 2]
+            .into_iter()
+            .filter(|&part| {
+                self.answers
+                    .get(Key {
+                        year: self.year,
+                        day,
+                        part,
+                    })
+                    .is_some()
+            })
+            .count()
+    }
+
+    fn push_log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > MAX_LOG_LINES {
+            self.log.drain(..self.log.len() - MAX_LOG_LINES);
+        }
+    }
+
+    fn push_error(&mut self, day: u32, message: String) {
+        self.push_log(format!("[ERROR day{day:02}] {message}"));
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [days, side] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(main);
+        let [details, logs] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(side);
+
+        self.render_days(frame, days);
+        self.render_details(frame, details);
+        self.render_log(frame, logs);
+
+        let input = if self.example { "example" } else { "cached" };
+        frame.render_widget(
+            Line::from(format!(
+                " ↑/↓ select  r run  t test  b bench  e input: {input}  c clear log  q quit"
+            ))
+            .dark_gray(),
+            help,
+        );
+    }
+
+    fn render_days(&self, frame: &mut Frame, area: Rect) {
+        let header = Row::new([
+            "Day", "Stars", "Part 1", "Part 2", "Status", "Time", "Tests",
+        ])
+        .add_modifier(Modifier::BOLD);
+
+        let rows = self.days.iter().map(|day| {
+            let stars = self.stars(day.day);
+            let stars = Span::from(format!("{:<2}", "*".repeat(stars))).yellow();
+
+            let answer = |part: u32| {
+                day.report
+                    .as_ref()
+                    .and_then(|report| report.parts.iter().find(|p| p.part == part))
+                    .map_or(String::new(), |part| match &part.answer {
+                        Ok(answer) => answer.letters().encode(),
+                        Err(failure) => failure.kind().to_string(),
+                    })
+            };
+
+            let (status, color) = match (day.busy, &day.report) {
+                (Some(action), _) => (format!("{action:?}…").to_lowercase(), Color::Cyan),
+                (None, Some(report)) => self.summary(report),
+                (None, None) if day.puzzle.is_none() => (String::new(), Color::Reset),
+                (None, None) => ("-".to_string(), Color::DarkGray),
+            };
+
+            let time = day.report.as_ref().map_or(String::new(), |report| {
+                let total = report.parse.as_ref().map_or(Duration::ZERO, |s| s.median)
+                    + report
+                        .parts
+                        .iter()
+                        .map(|part| part.time.median)
+                        .sum::<Duration>();
+
+                format!("{total:.2?}")
+            });
+
+            let tests = day.tests.as_ref().map_or(Span::from(""), |checked| {
+                let passed = checked.iter().filter(|checked| checked.passed()).count();
+                let tests = Span::from(format!("{passed}/{}", checked.len()));
+                if passed == checked.len() {
+                    tests.green()
+                } else {
+                    tests.red()
+                }
+            });
+
+            let row = Row::new(vec![
+                Span::from(format!("{:>3}", day.day)),
+                stars,
+                Span::from(answer(1)),
+                Span::from(answer(2)),
+                Span::from(status).fg(color),
+                Span::from(time),
+                tests,
+            ]);
+
+            if day.puzzle.is_none() {
+                row.dark_gray()
+            } else {
+                row
+            }
+        });
+
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(5),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(format!(" Advent of Code {} ", self.year)))
+            .row_highlight_style(Style::new().reversed());
+
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    /// The overall status of a report and its colour: the first failure,
+    /// otherwise whether every answer matches the recorded one.
+    fn summary(&self, report: &Report) -> (String, Color) {
+        let mut verified = true;
+        for part in &report.parts {
+            let answer = match &part.answer {
+                Ok(answer) => answer,
+                Err(failure) => return (failure.kind().to_string(), Color::Red),
+            };
+
+            let key = Key {
+                year: report.year,
+                day: report.day,
+                part: part.part,
+            };
+            match self.answers.check(key, answer) {
+                Status::Correct => (),
+                Status::Incorrect { .. } => return ("mismatch".to_string(), Color::Red),
+                Status::Unknown => verified = false,
+            }
+        }
+
+        if verified {
+            ("ok".to_string(), Color::Green)
+        } else {
+            ("unverified".to_string(), Color::Yellow)
+        }
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let day = &self.days[self.selected];
+        let mut lines = Vec::new();
+
+        match &day.report {
+            Some(report) => {
+                if report.cached {
+                    lines.push(Line::from("Cached, press r to solve again").dark_gray());
+                }
+                lines.push(Line::from(format!("load    {:>10.2?}", report.load)));
+                if let Some(parse) = &report.parse {
+                    lines.push(Line::from(format!("parse   {parse}")));
+                }
+                for part in &report.parts {
+                    lines.push(Line::from(format!(
+                        "part {}  {}  {}",
+                        part.part,
+                        part.time,
+                        part.status(self.year, day.day, &self.answers)
+                    )));
+                    if let Some(usage) = &part.alloc {
+                        lines.push(Line::from(format!("        {usage}")));
+                    }
+                    if let Ok(answer) = &part.answer {
+                        lines.extend(
+                            answer
+                                .to_string()
+                                .lines()
+                                .map(|line| Line::from(format!("        {line}")).bold()),
+                        );
+                    }
+                }
+            }
+            None if day.puzzle.is_some() => lines.push(Line::from("Press r to run").dark_gray()),
+            None => lines.push(Line::from("Not solved yet").dark_gray()),
+        }
+
+        if let Some(checked) = &day.tests {
+            lines.push(Line::default());
+            for checked in checked {
+                let line = Line::from(checked.to_string());
+                lines.push(if checked.passed() {
+                    line.green()
+                } else {
+                    line.red()
+                });
+            }
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(Block::bordered().title(format!(" Day {} ", day.day)))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_log(&self, frame: &mut Frame, area: Rect) {
+        let visible = usize::from(area.height.saturating_sub(2));
+        let lines: Vec<Line> = self.log[self.log.len().saturating_sub(visible)..]
+            .iter()
+            .map(|line| {
+                let line = Line::from(line.as_str());
+                if line_level(&line).is_some_and(|level| level <= log::Level::Error) {
+                    line.red()
+                } else if line_level(&line) == Some(log::Level::Warn) {
+                    line.yellow()
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Log ")),
+            area,
+        );
+    }
+}
+
+/// The level of a line written by the logging macros, e.g. `[WARN  day05] …`.
+fn line_level(line: &Line) -> Option<log::Level> {
+    let text = line.spans.first()?.content.strip_prefix('[')?;
+
+    text.split_whitespace().next()?.parse().ok()
+}
+
+/// Runs the action on a day. Only uses cached inputs, so nothing is fetched
+/// while the dashboard owns the terminal.
+fn work(puzzle: &Puzzle, action: Action, example: bool) -> Outcome {
+    if action == Action::Test {
+        return Outcome::Tests(examples::verify(puzzle));
+    }
+
+    let start = Instant::now();
+    let input = if example {
+        let path = examples::path(puzzle.year, puzzle.day, examples::DEFAULT_EXAMPLE);
+        fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+    } else {
+        get_cached_input(puzzle.year, puzzle.day).ok_or(format!(
+            "No cached input, run `aoc run {} --year {}` to fetch it",
+            puzzle.day, puzzle.year
+        ))
+    };
+    let load = start.elapsed();
+
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => return Outcome::Error(e),
+    };
+
+    let options = Options {
+        repeat: if action == Action::Bench {
+            BENCH_REPEAT
+        } else {
+            1
+        },
+        example,
+        ..Options::default()
+    };
+    let mut report = puzzle.solve(&input, &options);
+    report.load = load;
+
+    Outcome::Report(report)
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, crossterm::event::KeyCode, Terminal};
+
+    use super::{Action, Dashboard, Outcome};
+    use crate::{
+        answers::{Answers, Key},
+        examples, puzzle, y2024,
+    };
+
+    fn dashboard() -> Dashboard {
+        let mut answers = Answers::default();
+        answers
+            .insert(
+                Key {
+                    year: 2024,
+                    day: 4,
+                    part: 1,
+                },
+                18,
+            )
+            .unwrap();
+
+        Dashboard::new(2024, y2024::ALL, answers)
+    }
+
+    #[test]
+    fn keys_select_and_start() {
+        let mut dashboard = dashboard();
+
+        assert_eq!(dashboard.handle_key(KeyCode::Up), None);
+        assert_eq!(dashboard.selected, 0);
+        assert_eq!(dashboard.handle_key(KeyCode::Char('r')), Some(Action::Run));
+
+        dashboard.handle_key(KeyCode::Char('e'));
+        assert!(dashboard.example);
+
+        dashboard.selected = 20;
+        assert_eq!(dashboard.handle_key(KeyCode::Char('b')), None);
+        assert_eq!(dashboard.log.len(), 1);
+
+        dashboard.handle_key(KeyCode::Char('q'));
+        assert!(dashboard.quit);
+    }
+
+    #[test]
+    fn count_stars() {
+        let dashboard = dashboard();
+
+        assert_eq!(dashboard.stars(4), 1);
+        assert_eq!(dashboard.stars(5), 0);
+    }
+
+    #[test]
+    fn render_test_results() {
+        let mut dashboard = dashboard();
+        dashboard.selected = 3;
+        dashboard.finish(
+            4,
+            Outcome::Tests(examples::verify(puzzle(2024, 4).unwrap())),
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| dashboard.render(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(screen.contains("Advent of Code 2024"));
+        assert!(screen.contains("5/5"));
+        assert!(screen.contains("horizontal part 1: expected 1, got 1"));
+    }
+
+    #[test]
+    fn ignore_unknown_days() {
+        let mut dashboard = dashboard();
+        dashboard.finish(26, Outcome::Error("failed".to_string()));
+
+        assert!(dashboard.days.iter().all(|day| day.report.is_none()));
+        assert_eq!(
+            dashboard.log,
+            ["[ERROR day26] finished but is not on the dashboard"]
+        );
+    }
+}
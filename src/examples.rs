@@ -0,0 +1,264 @@
+//! Example inputs from the puzzle descriptions. Each day keeps its examples as
+//! named files, `input/{year}/examples/dayDD/{name}.txt`, next to an
+//! `expected.txt` manifest with one `name part answer` line per expected
+//! answer. The example named [`DEFAULT_EXAMPLE`] is the one solved by
+//! `--example`.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::PathBuf,
+};
+
+use crate::{
+    input_dir,
+    runner::{Failure, Options},
+    Answer, Puzzle, Solution,
+};
+
+/// The directory under the year's [`input_dir`] that holds its examples.
+pub const EXAMPLES_DIR: &str = "examples";
+
+pub const MANIFEST: &str = "expected.txt";
+
+pub const DEFAULT_EXAMPLE: &str = "example";
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// Expected answers by part, for the parts listed in the manifest.
+    pub expected: BTreeMap<u32, Answer>,
+}
+
+pub fn dir(year: u32, day: u32) -> PathBuf {
+    input_dir(year)
+        .join(EXAMPLES_DIR)
+        .join(format!("day{day:02}"))
+}
+
+pub fn path(year: u32, day: u32, name: &str) -> PathBuf {
+    dir(year, day).join(format!("{name}.txt"))
+}
+
+/// Loads every example of the day with its expected answers, sorted by name.
+pub fn load(year: u32, day: u32) -> Result<Vec<Example>, String> {
+    let dir = dir(year, day);
+    let entries =
+        fs::read_dir(&dir).map_err(|e| format!("Could not read {}: {e}", dir.display()))?;
+
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|ext| ext != "txt") || path.ends_with(MANIFEST) {
+            continue;
+        }
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+
+        examples.push(Example {
+            name,
+            input,
+            expected: BTreeMap::new(),
+        });
+    }
+
+    let manifest_path = dir.join(MANIFEST);
+    let manifest = match fs::read_to_string(&manifest_path) {
+        Ok(content) => parse_manifest(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+    .map_err(|e| format!("Could not load {}: {e}", manifest_path.display()))?;
+
+    for (name, part, answer) in manifest {
+        let example = examples
+            .iter_mut()
+            .find(|example| example.name == name)
+            .ok_or(format!(
+                "{}: no example named {name}",
+                manifest_path.display()
+            ))?;
+
+        example.expected.insert(part, answer);
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(examples)
+}
+
+/// Parses `name part answer` lines, skipping blank lines and `#` comments.
+/// Newlines in multi-line answers are written as `\n`.
+pub fn parse_manifest(content: &str) -> Result<Vec<(String, u32, Answer)>, String> {
+    let mut entries = Vec::new();
+
+    for (line_no, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, ' ');
+        let name = fields.next().unwrap_or_default();
+        let part = fields
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or(format!("line {}: invalid part", line_no + 1))?;
+        let Some(answer) = fields.next().filter(|answer| !answer.is_empty()) else {
+            return Err(format!("line {}: missing answer", line_no + 1));
+        };
+
+        entries.push((name.to_string(), part, Answer::decode(answer)));
+    }
+
+    Ok(entries)
+}
+
+/// The answer to one part of an example next to the expected one.
+#[derive(Debug)]
+pub struct Checked {
+    pub example: String,
+    pub part: u32,
+    pub expected: Answer,
+    pub actual: Result<Answer, Failure>,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        self.actual
+            .as_ref()
+            .is_ok_and(|actual| actual.matches(&self.expected))
+    }
+}
+
+impl Display for Checked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actual = match &self.actual {
+            Ok(answer) => answer.encode(),
+            Err(failure) => failure.to_string(),
+        };
+
+        write!(
+            f,
+            "{} part {}: expected {}, got {actual}",
+            self.example,
+            self.part,
+            self.expected.encode()
+        )
+    }
+}
+
+/// Solves every example of a registered day for the parts its manifest has
+/// answers for. Panics are caught and reported as failed checks.
+pub fn verify(puzzle: &Puzzle) -> Result<Vec<Checked>, String> {
+    let mut checked = Vec::new();
+
+    for example in load(puzzle.year, puzzle.day)? {
+        let parts: Vec<u32> = example.expected.keys().copied().collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let report = puzzle.solve_parts(&example.input, &parts, &Options::default());
+        for part in report.parts {
+            checked.push(Checked {
+                example: example.name.clone(),
+                part: part.part,
+                expected: example.expected[&part.part].clone(),
+                actual: part.answer,
+            });
+        }
+    }
+
+    Ok(checked)
+}
+
+/// Solves every example of the day that has an expected answer for the part
+/// and asserts that they all match. Used by the tests that
+/// [`example_tests!`] generates.
+///
+/// [`example_tests!`]: crate::example_tests
+pub fn check<S: Solution>(part: u32) {
+    let checked: Vec<_> = verify(&Puzzle::new::<S>())
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
+        .filter(|checked| checked.part == part)
+        .collect();
+    let failures: Vec<_> = checked
+        .iter()
+        .filter(|checked| !checked.passed())
+        .map(|checked| format!("  {checked}"))
+        .collect();
+
+    assert!(
+        !checked.is_empty(),
+        "No expected answers for day {} part {part} in {}",
+        S::DAY,
+        dir(S::YEAR, S::DAY).join(MANIFEST).display()
+    );
+    assert!(
+        failures.is_empty(),
+        "day {} part {part} examples failed:\n{}",
+        S::DAY,
+        failures.join("\n")
+    );
+}
+
+/// Generates a test per part that solves the day's examples and checks them
+/// against its manifest, so a new example is a data change rather than a new
+/// test.
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        #[test]
+        fn part1_examples() {
+            $crate::examples::check::<$solution>(1);
+        }
+
+        #[test]
+        fn part2_examples() {
+            $crate::examples::check::<$solution>(2);
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{load, parse_manifest};
+    use crate::Answer;
+
+    #[test]
+    fn parse_manifest_lines() {
+        let content = "# name part answer\nexample 1 18\n\nmas_x 2 1\n";
+
+        assert_eq!(
+            parse_manifest(content).unwrap(),
+            [
+                ("example".to_string(), 1, Answer::Unsigned(18)),
+                ("mas_x".to_string(), 2, Answer::Unsigned(1)),
+            ]
+        );
+        assert!(parse_manifest("example one 18").is_err());
+        assert!(parse_manifest("example 1").is_err());
+    }
+
+    #[test]
+    fn load_named_examples() {
+        let examples = load(2024, 4).unwrap();
+        let names: Vec<_> = examples
+            .iter()
+            .map(|example| example.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["example", "horizontal", "mas_x", "vertical"]);
+        assert_eq!(examples[0].expected[&1], Answer::from(18));
+        assert!(!examples[2].expected.contains_key(&1));
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+/// A position in a [`Grid`] as `(row, column)`, counted from the top left.
+pub type Pos = (usize, usize);
+
+/// A rectangular grid of cells stored row by row in one `Vec`, as most
+/// puzzles draw their maps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the cells in row-major order, which must fill it exactly.
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, String> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(format!(
+                "{} cells do not fill rows {width} wide",
+                cells.len()
+            ));
+        }
+
+        Ok(Self {
+            height: cells.len() / width,
+            cells,
+            width,
+        })
+    }
+
+    /// Parses lines of text, mapping each character to a cell.
+    pub fn from_lines(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+        mut cell: impl FnMut(char) -> T,
+    ) -> Result<Self, String> {
+        Self::from_rows(
+            lines
+                .into_iter()
+                .map(|line| line.as_ref().chars().map(&mut cell).collect()),
+        )
+    }
+
+    /// Parses newline separated rows of bytes, mapping each byte to a cell.
+    pub fn from_bytes(input: &[u8], mut cell: impl FnMut(u8) -> T) -> Result<Self, String> {
+        Self::from_rows(
+            input
+                .split(|&byte| byte == b'\n')
+                .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+                .map(|line| line.iter().copied().map(&mut cell).collect()),
+        )
+    }
+
+    /// Builds the grid from its rows. Blank rows at the end, as left by a
+    /// trailing newline, are skipped, and every other row must be as wide as
+    /// the first.
+    fn from_rows(rows: impl Iterator<Item = Vec<T>>) -> Result<Self, String> {
+        let mut rows: Vec<Vec<T>> = rows.collect();
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err("The grid is empty".to_string());
+        }
+        if let Some(r) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {r} is {} wide, the first is {width}",
+                rows[r].len()
+            ));
+        }
+
+        Ok(Self {
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            width,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (r, c): Pos) -> bool {
+        r < self.height && c < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    /// The position `(dr, dc)` away from `pos`, if it is on the grid.
+    pub fn offset(&self, (r, c): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The cell `(dr, dc)` away from `pos`, if it is on the grid.
+    pub fn get_offset(&self, pos: Pos, delta: (isize, isize)) -> Option<&T> {
+        self.get(self.offset(pos, delta)?)
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.width)
+    }
+
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(c < self.width, "Column {c} is outside the grid");
+
+        self.cells[c..].iter().step_by(self.width)
+    }
+
+    pub fn column_mut(&mut self, c: usize) -> impl Iterator<Item = &mut T> {
+        assert!(c < self.width, "Column {c} is outside the grid");
+
+        self.cells[c..].iter_mut().step_by(self.width)
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        let width = self.width;
+
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    /// The position of the first cell, row by row, that matches.
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells
+            .iter()
+            .position(f)
+            .map(|i| (i / self.width, i % self.width))
+    }
+
+    /// A grid of the same shape with every cell mapped.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Draws each row as a line of text.
+    pub fn lines(&self, mut f: impl FnMut(&T) -> char) -> Vec<String> {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect())
+            .collect()
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `value`. Panics if `width` is zero, which
+    /// [`Grid::new`] rejects too.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        assert!(width > 0, "A grid cannot be 0 wide");
+
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {width}x{height} grid"))
+    }
+}
+
+/// Prints the grid one row per line, with the cells side by side.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+
+    fn grid() -> Grid<char> {
+        Grid::from_lines("abc\ndef".lines(), |c| c).unwrap()
+    }
+
+    #[test]
+    fn parse_lines_and_bytes() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(
+            Grid::from_bytes(b"#.\r\n.#\n\n", |b| b == b'#').unwrap(),
+            Grid::new(2, vec![true, false, false, true]).unwrap()
+        );
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn reject_ragged_rows() {
+        assert!(Grid::from_lines(["abc", "de"], |c| c).is_err());
+        assert!(Grid::from_bytes(b"\n", |b| b).is_err());
+        assert!(Grid::new(2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn bounds_and_offsets() {
+        let grid = grid();
+
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.offset((1, 1), (-1, 1)), Some((0, 2)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+        assert_eq!(grid.get_offset((0, 0), (1, 2)), Some(&'f'));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut grid = grid();
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+
+        grid.column_mut(2).for_each(|cell| *cell = '#');
+        grid.row_mut(0)[0] = '#';
+        grid[(1, 0)] = '.';
+
+        assert_eq!(grid.lines(|&c| c), ["#b#", ".e#"]);
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.cells().filter(|(_, &c)| c == '#').count(), 3);
+    }
+
+    #[test]
+    fn map_and_fill() {
+        let upper = grid().map(char::to_ascii_uppercase);
+
+        assert_eq!(upper.row(0), ['A', 'B', 'C']);
+        assert_eq!(Grid::filled(2, 3, 0).cells().count(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "A grid cannot be 0 wide")]
+    fn fill_zero_wide() {
+        Grid::filled(0, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "(2, 0) is outside the 3x2 grid")]
+    fn index_out_of_bounds() {
+        let _ = grid()[(2, 0)];
+    }
+}
@@ -0,0 +1,106 @@
+//! Every input cached for a day, across accounts. Besides the default
+//! `input/{year}/input{day}`, whose answers are in [`ANSWERS_PATH`], an
+//! account can keep its inputs in a profile directory as
+//! `input/{year}/{account}/input{day}` and extra inputs can be dropped in as
+//! `input/{year}/input{day}.{account}`. Either way the account's answers are
+//! read from `input/{year}/{account}/answers.txt`.
+
+use std::{fs, path::PathBuf};
+
+use crate::{
+    answers::{Answers, ANSWERS_PATH},
+    input_dir,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct Source {
+    pub year: u32,
+    /// `None` for the default account.
+    pub account: Option<String>,
+    pub path: PathBuf,
+}
+
+impl Source {
+    pub fn account_name(&self) -> &str {
+        self.account.as_deref().unwrap_or("default")
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.account {
+            Some(account) => input_dir(self.year).join(account).join(ANSWERS_PATH),
+            None => PathBuf::from(ANSWERS_PATH),
+        }
+    }
+
+    pub fn load_answers(&self) -> Result<Answers, String> {
+        let path = self.answers_path();
+
+        Answers::load(&path).map_err(|e| format!("Could not load {}: {e}", path.display()))
+    }
+}
+
+/// Finds every cached input for `day`, the default account's first.
+pub fn sources(year: u32, day: u32) -> Vec<Source> {
+    let Ok(entries) = fs::read_dir(input_dir(year)) else {
+        return Vec::new();
+    };
+
+    let mut sources = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            let input = path.join(format!("input{day}"));
+            if input.is_file() {
+                sources.push(Source {
+                    year,
+                    account: Some(name),
+                    path: input,
+                });
+            }
+        } else if let Some((file_day, account)) = parse_file_name(&name) {
+            if file_day == day {
+                sources.push(Source {
+                    year,
+                    account,
+                    path,
+                });
+            }
+        }
+    }
+
+    sources.sort_by(|a, b| (&a.account, &a.path).cmp(&(&b.account, &b.path)));
+
+    sources
+}
+
+/// Reads the day and account from an input file name, skipping examples.
+fn parse_file_name(name: &str) -> Option<(u32, Option<String>)> {
+    let rest = name.strip_prefix("input")?;
+    let (day, account) = match rest.split_once('.') {
+        Some((_, "test")) => return None,
+        Some((day, account)) if !account.is_empty() => (day, Some(account.to_string())),
+        Some(_) => return None,
+        None => (rest, None),
+    };
+
+    Some((day.parse().ok()?, account))
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_file_name;
+
+    #[test]
+    fn parse_input_file_names() {
+        assert_eq!(parse_file_name("input6"), Some((6, None)));
+        assert_eq!(
+            parse_file_name("input12.alice"),
+            Some((12, Some("alice".to_string())))
+        );
+        assert_eq!(parse_file_name("input6.test"), None);
+        assert_eq!(parse_file_name("input6."), None);
+        assert_eq!(parse_file_name("notes.txt"), None);
+    }
+}
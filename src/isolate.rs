@@ -0,0 +1,273 @@
+use std::{
+    env,
+    io::{Read, Write},
+    process::{self, Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    alloc::Usage,
+    json,
+    runner::{Failure, Options, PartReport, Report, Stats},
+    Answer, Puzzle,
+};
+
+/// Set on a child process to make it solve a single part, as
+/// `year:day:part:repeat`.
+const CHILD_ENV: &str = "AOC_ISOLATED_PART";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Called first thing by every binary that runs puzzles. When this process
+/// was started by [`solve`], solves the requested part of the input on stdin,
+/// prints the result as JSON and exits.
+pub fn serve<'a>(puzzles: impl IntoIterator<Item = &'a Puzzle>) {
+    let Ok(request) = env::var(CHILD_ENV) else {
+        return;
+    };
+
+    let fields: Vec<u32> = request.split(':').filter_map(|f| f.parse().ok()).collect();
+    let &[year, day, part, repeat] = fields.as_slice() else {
+        panic!("Invalid {CHILD_ENV}: {request}");
+    };
+    let puzzle = puzzles
+        .into_iter()
+        .find(|puzzle| puzzle.year == year && puzzle.day == day)
+        .unwrap_or_else(|| panic!("Day {day} of {year} is not registered"));
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .expect("Could not read input from stdin");
+
+    let options = Options {
+        repeat: repeat as usize,
+        ..Options::default()
+    };
+    let report = puzzle.solve_parts(&input, &[part], &options);
+    let solved = &report.parts[0];
+
+    let panic = match &solved.answer {
+        Err(Failure::Panicked { message, location }) => {
+            Some(json!({ "message": message, "location": location }))
+        }
+        _ => None,
+    };
+
+    let result = json!({
+        "answer": solved.answer.as_ref().ok().map(ToString::to_string),
+        "panic": panic,
+        "parse": report.parse.as_ref().map(json::stats),
+        "parse_alloc": report.parse_alloc.as_ref().map(json::usage),
+        "time": json::stats(&solved.time),
+        "alloc": solved.alloc.as_ref().map(json::usage),
+    });
+    println!("{result}");
+
+    process::exit(0);
+}
+
+/// Solves each part of the puzzle in a child process running this same
+/// executable, killing it if it exceeds the timeout and limiting its memory.
+pub fn solve(puzzle: &Puzzle, input: &str, options: &Options) -> Report {
+    let mut report = Report {
+        year: puzzle.year,
+        day: puzzle.day,
+        load: Duration::ZERO,
+        parse: None,
+        parse_alloc: None,
+        parts: Vec::new(),
+        cached: false,
+    };
+
+    for &part in options.parts() {
+        let start = Instant::now();
+        let solved = match solve_part(puzzle, part, input, options) {
+            Ok(solved) => {
+                if report.parse.is_none() {
+                    report.parse = Some(solved.parse);
+                    report.parse_alloc = solved.parse_alloc;
+                }
+
+                PartReport {
+                    part,
+                    answer: Ok(Answer::from(solved.answer)),
+                    time: solved.time,
+                    alloc: solved.alloc,
+                }
+            }
+            Err(failure) => PartReport {
+                part,
+                answer: Err(failure),
+                time: Stats::from_samples(vec![start.elapsed()]),
+                alloc: None,
+            },
+        };
+
+        report.parts.push(solved);
+    }
+
+    report
+}
+
+/// What a child process reports back after solving a part.
+struct Solved {
+    answer: String,
+    parse: Stats,
+    parse_alloc: Option<Usage>,
+    time: Stats,
+    alloc: Option<Usage>,
+}
+
+fn solve_part(
+    puzzle: &Puzzle,
+    part: u32,
+    input: &str,
+    options: &Options,
+) -> Result<Solved, Failure> {
+    let mut child = spawn(puzzle, part, options).map_err(Failure::Crashed)?;
+
+    let mut stdin = child.stdin.take().expect("Child has stdin");
+    let input = input.to_string();
+    // The child may die before reading all of it, so errors are ignored.
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(input.as_bytes());
+    });
+
+    let mut stdout = child.stdout.take().expect("Child has stdout");
+    let stdout = thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stdout.read_to_string(&mut buf);
+        buf
+    });
+    let mut stderr = child.stderr.take().expect("Child has stderr");
+    let stderr = thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let status = wait(&mut child, options.timeout);
+
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    // Pass on anything the solution logged.
+    eprint!("{stderr}");
+
+    let status = status?;
+    if stderr.contains("memory allocation of") {
+        return Err(Failure::OutOfMemory);
+    }
+    if !status.success() {
+        let message = stderr
+            .lines()
+            .find(|line| line.contains("panicked at"))
+            .map(str::to_string)
+            .unwrap_or(status.to_string());
+
+        return Err(Failure::Crashed(message));
+    }
+
+    parse_result(&stdout)
+}
+
+fn spawn(puzzle: &Puzzle, part: u32, options: &Options) -> Result<Child, String> {
+    let exe = env::current_exe().map_err(|e| format!("Could not find executable: {e}"))?;
+
+    let mut command = Command::new(exe);
+    command
+        .env(
+            CHILD_ENV,
+            format!("{}:{}:{part}:{}", puzzle.year, puzzle.day, options.repeat),
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    if let Some(memory) = options.memory {
+        limit_memory(&mut command, memory);
+    }
+
+    command
+        .spawn()
+        .map_err(|e| format!("Could not start child process: {e}"))
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+
+    // SAFETY: setrlimit is async-signal-safe and only touches the child.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+/// Never called, as [`Options::from_args`] rejects `--memory` off unix.
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {}
+
+fn wait(child: &mut Child, timeout: Option<Duration>) -> Result<process::ExitStatus, Failure> {
+    let start = Instant::now();
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) => (),
+            Err(e) => return Err(Failure::Crashed(e.to_string())),
+        }
+
+        if let Some(timeout) = timeout.filter(|timeout| start.elapsed() >= *timeout) {
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(Failure::Timeout(timeout));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn parse_result(stdout: &str) -> Result<Solved, Failure> {
+    let result: Option<Value> = stdout
+        .lines()
+        .rfind(|line| line.starts_with('{'))
+        .and_then(|line| serde_json::from_str(line).ok());
+    let invalid = || Failure::Crashed("Invalid output from child".to_string());
+    let result = result.ok_or_else(invalid)?;
+
+    // The child caught a panic in the solution.
+    if let Some(message) = result["panic"]["message"].as_str() {
+        return Err(Failure::Panicked {
+            message: message.to_string(),
+            location: result["panic"]["location"].as_str().map(str::to_string),
+        });
+    }
+
+    solved(&result).ok_or_else(invalid)
+}
+
+fn solved(result: &Value) -> Option<Solved> {
+    Some(Solved {
+        answer: result["answer"].as_str()?.to_string(),
+        parse: json::parse_stats(&result["parse"])?,
+        parse_alloc: json::parse_usage(&result["parse_alloc"]),
+        time: json::parse_stats(&result["time"])?,
+        alloc: json::parse_usage(&result["alloc"]),
+    })
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::{
+    alloc::Usage,
+    answers::{Answers, Key, Status},
+    runner::{Format, Report, Stats},
+};
+
+/// One record per part of the report, with the verification status against
+/// the recorded answers.
+pub fn report_records(report: &Report, answers: &Answers) -> Vec<Value> {
+    report
+        .parts
+        .iter()
+        .map(|part| {
+            let (status, expected, error) = match &part.answer {
+                Ok(answer) => {
+                    let key = Key {
+                        year: report.year,
+                        day: report.day,
+                        part: part.part,
+                    };
+
+                    match answers.check(key, answer) {
+                        Status::Correct => ("correct", None, None),
+                        Status::Incorrect { expected } => {
+                            ("incorrect", Some(expected.to_string()), None)
+                        }
+                        Status::Unknown => ("unknown", None, None),
+                    }
+                }
+                Err(failure) => (failure.kind(), None, Some(failure.to_string())),
+            };
+
+            json!({
+                "year": report.year,
+                "day": report.day,
+                "part": part.part,
+                "answer": part.answer.as_ref().ok().map(ToString::to_string),
+                "status": status,
+                "expected": expected,
+                "timings": {
+                    "load_ns": nanos(report.load),
+                    "parse": report.parse.as_ref().map(stats),
+                    "part": stats(&part.time),
+                },
+                "allocations": {
+                    "parse": report.parse_alloc.as_ref().map(usage),
+                    "part": part.alloc.as_ref().map(usage),
+                },
+                "error": error,
+                "cached": report.cached,
+            })
+        })
+        .collect()
+}
+
+/// A record for a day that could not be solved at all.
+pub fn error_record(year: u32, day: u32, error: &str) -> Value {
+    json!({
+        "year": year,
+        "day": day,
+        "part": null,
+        "answer": null,
+        "status": "error",
+        "expected": null,
+        "timings": null,
+        "allocations": null,
+        "error": error,
+        "cached": false,
+    })
+}
+
+/// Prints the records as a single JSON array or as one record per line.
+pub fn print(records: &[Value], format: Format) {
+    match format {
+        Format::Json => println!("{}", Value::from(records)),
+        Format::Ndjson => {
+            for record in records {
+                println!("{record}");
+            }
+        }
+        Format::Text => panic!("Text output is not JSON"),
+    }
+}
+
+pub(crate) fn stats(stats: &Stats) -> Value {
+    json!({
+        "min_ns": nanos(stats.min),
+        "median_ns": nanos(stats.median),
+        "mean_ns": nanos(stats.mean),
+        "std_dev_ns": nanos(stats.std_dev),
+        "runs": stats.runs,
+    })
+}
+
+pub(crate) fn parse_stats(value: &Value) -> Option<Stats> {
+    let duration = |name: &str| value[name].as_u64().map(Duration::from_nanos);
+
+    Some(Stats {
+        min: duration("min_ns")?,
+        median: duration("median_ns")?,
+        mean: duration("mean_ns")?,
+        std_dev: duration("std_dev_ns")?,
+        runs: value["runs"].as_u64()? as usize,
+    })
+}
+
+pub(crate) fn usage(usage: &Usage) -> Value {
+    json!({
+        "allocations": usage.allocations,
+        "bytes": usage.bytes,
+        "peak_bytes": usage.peak,
+    })
+}
+
+pub(crate) fn parse_usage(value: &Value) -> Option<Usage> {
+    Some(Usage {
+        allocations: value["allocations"].as_u64()?,
+        bytes: value["bytes"].as_u64()?,
+        peak: value["peak_bytes"].as_u64()?,
+    })
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{
+        alloc::Usage,
+        answers::{Answers, Key},
+        runner::{Failure, PartReport, Report, Stats},
+        Answer,
+    };
+
+    use super::{error_record, parse_stats, parse_usage, report_records, stats, usage};
+
+    fn micros(micros: u64) -> Stats {
+        Stats::from_samples(vec![Duration::from_micros(micros)])
+    }
+
+    #[test]
+    fn records_for_report() {
+        let report = Report {
+            year: 2024,
+            day: 4,
+            load: Duration::from_micros(1),
+            parse: Some(micros(2)),
+            parse_alloc: None,
+            parts: vec![
+                PartReport {
+                    part: 1,
+                    answer: Ok(Answer::from(18)),
+                    time: micros(3),
+                    alloc: None,
+                },
+                PartReport {
+                    part: 2,
+                    answer: Ok(Answer::from(9)),
+                    time: micros(4),
+                    alloc: None,
+                },
+            ],
+            cached: false,
+        };
+        let mut answers = Answers::default();
+        answers
+            .insert(
+                Key {
+                    year: 2024,
+                    day: 4,
+                    part: 2,
+                },
+                "8",
+            )
+            .unwrap();
+
+        let records = report_records(&report, &answers);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["status"], "unknown");
+        assert_eq!(records[0]["timings"]["part"]["median_ns"], 3000);
+        assert_eq!(records[1]["answer"], "9");
+        assert_eq!(records[1]["status"], "incorrect");
+        assert_eq!(records[1]["expected"], "8");
+    }
+
+    #[test]
+    fn record_for_failed_part() {
+        let report = Report {
+            year: 2024,
+            day: 6,
+            load: Duration::ZERO,
+            parse: None,
+            parse_alloc: None,
+            parts: vec![PartReport {
+                part: 2,
+                answer: Err(Failure::Timeout(Duration::from_secs(1))),
+                time: micros(1_000_000),
+                alloc: None,
+            }],
+            cached: false,
+        };
+
+        let records = report_records(&report, &Answers::default());
+
+        assert_eq!(records[0]["status"], "timeout");
+        assert!(records[0]["answer"].is_null());
+        assert!(records[0]["timings"]["parse"].is_null());
+    }
+
+    #[test]
+    fn stats_round_trip() {
+        let expected = micros(5);
+
+        assert_eq!(parse_stats(&stats(&expected)), Some(expected));
+    }
+
+    #[test]
+    fn usage_round_trip() {
+        let expected = Usage {
+            allocations: 3,
+            bytes: 1024,
+            peak: 512,
+        };
+
+        assert_eq!(parse_usage(&usage(&expected)), Some(expected));
+    }
+
+    #[test]
+    fn record_for_error() {
+        let record = error_record(2024, 3, "no cached input");
+
+        assert_eq!(record["status"], "error");
+        assert_eq!(record["error"], "no cached input");
+        assert!(record["answer"].is_null());
+    }
+}
@@ -1,34 +1,99 @@
+//! Solutions to Advent of Code, one module per year such as [`y2024`], on a
+//! shared core: fetching and caching inputs, examples, answers, the runner
+//! and the tools built on it. Each year's inputs and examples are kept under
+//! `input/{year}`.
+
 use std::{
     env,
-    fs::File,
+    fs::{self, File},
     io::{BufRead, BufReader, Seek, Write},
+    path::{Path, PathBuf},
 };
 
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder};
+
+pub mod alloc;
+mod answer;
+pub mod answers;
+pub mod cache;
+pub mod dashboard;
+pub mod examples;
+mod grid;
+pub mod inputs;
+pub mod isolate;
+pub mod json;
+pub mod log;
+pub mod ocr;
+pub mod perf;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+mod solution;
+pub mod submit;
+pub mod watch;
+pub mod y2024;
+
+pub use answer::Answer;
+pub use grid::{Grid, Pos};
+pub use solution::{Implementation, Puzzle, Solution};
+
+/// Every year with solutions, oldest first.
+pub const YEARS: &[(u32, &[Puzzle])] = &[(2024, y2024::ALL)];
+
+/// The days of each year's calendar, numbered from 1.
+pub const DAYS: u32 = 25;
+
+/// The year commands run when none is given.
+pub const LATEST_YEAR: u32 = YEARS[YEARS.len() - 1].0;
+
+pub fn puzzles(year: u32) -> Option<&'static [Puzzle]> {
+    YEARS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map(|(_, puzzles)| *puzzles)
+}
+
+pub fn puzzle(year: u32, day: u32) -> Option<&'static Puzzle> {
+    puzzles(year)?.iter().find(|puzzle| puzzle.day == day)
+}
+
+/// Where the year's cached inputs and examples are kept.
+pub fn input_dir(year: u32) -> PathBuf {
+    Path::new("input").join(year.to_string())
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
 
-pub fn get_test_input(day: u32) -> impl BufRead {
-    let file = File::open(format!("input/input{}.test", day)).expect("Could not open file");
+pub fn get_test_input(year: u32, day: u32) -> impl BufRead {
+    let file = File::open(examples::path(year, day, examples::DEFAULT_EXAMPLE))
+        .expect("Could not open file");
 
     BufReader::new(file)
 }
 
-pub fn get_input(day: u32) -> impl BufRead {
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    input_dir(year).join(format!("input{day}"))
+}
+
+/// Reads the cached puzzle input for `day` without fetching it from the site.
+pub fn get_cached_input(year: u32, day: u32) -> Option<String> {
+    fs::read_to_string(input_path(year, day)).ok()
+}
+
+pub fn get_input(year: u32, day: u32) -> impl BufRead {
     dotenvy::dotenv().unwrap();
 
-    let path = format!("input/input{}", day);
+    let path = input_path(year, day);
     if let Ok(file) = File::open(&path) {
-        eprintln!("Reading input from file");
+        info!("Reading input from file");
         BufReader::new(file)
     } else {
-        eprintln!("Fetching input from site");
-        let client = Client::new();
-        let res = client
-            .get(format!("https://adventofcode.com/2024/day/{}/input", day))
-            .header("Cookie", env::var("COOKIE").unwrap())
-            .send()
-            .expect("Could not get input");
+        info!("Fetching input from site");
+        let content = fetch(year, &format!("day/{}/input", day)).expect("Could not get input");
 
-        let content = res.bytes().expect("Could not convert response to text");
+        fs::create_dir_all(input_dir(year)).expect("Could not create the input directory");
 
         let mut file = File::options()
             .create(true)
@@ -37,10 +102,37 @@ pub fn get_input(day: u32) -> impl BufRead {
             .write(true)
             .open(path)
             .unwrap();
-        file.write_all(&content).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
 
         file.rewind().unwrap();
 
         BufReader::new(file)
     }
 }
+
+/// Fetches a page of the year's puzzles from the site, e.g. `day/4/input`,
+/// authenticated with the session cookie from the environment.
+pub fn fetch(year: u32, path: &str) -> Result<String, String> {
+    send(Client::new().get(url(year, path)))
+}
+
+/// Posts a form to a page of the year's puzzles, e.g. `day/4/answer`.
+pub fn post(year: u32, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+    send(Client::new().post(url(year, path)).form(form))
+}
+
+fn url(year: u32, path: &str) -> String {
+    format!("https://adventofcode.com/{}/{}", year, path)
+}
+
+fn send(request: RequestBuilder) -> Result<String, String> {
+    dotenvy::dotenv().ok();
+    let cookie = env::var("COOKIE").map_err(|_| "COOKIE is not set".to_string())?;
+
+    request
+        .header("Cookie", cookie)
+        .send()
+        .and_then(|res| res.error_for_status())
+        .and_then(|res| res.text())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,253 @@
+//! Minimal levelled logging to stderr. Errors and warnings are shown unless
+//! the `AOC_LOG` environment variable says otherwise. The variable holds a
+//! comma separated list of either a default level or `target=level` pairs,
+//! e.g. `AOC_LOG=info`, `AOC_LOG=off` or `AOC_LOG=day05=trace`. A target is a module path, and matches by
+//! prefix or by its last segments, so `day05` enables the logs of
+//! `advent_of_code::y2024::day05`.
+
+use std::{
+    env,
+    fmt::{self, Arguments},
+    str::FromStr,
+    sync::{mpsc::Sender, Mutex, OnceLock},
+};
+
+pub const ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown log level: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+/// Warnings are worth seeing without asking for them.
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            default: Some(Level::Warn),
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    filter
+                        .targets
+                        .push((target.to_string(), parse_level(level)?));
+                }
+                None => match parse_level(directive) {
+                    Ok(level) => filter.default = level,
+                    // A bare target enables everything for it.
+                    Err(_) => filter
+                        .targets
+                        .push((directive.to_string(), Some(Level::Trace))),
+                },
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+fn parse_level(level: &str) -> Result<Option<Level>, String> {
+    if level.eq_ignore_ascii_case("off") {
+        Ok(None)
+    } else {
+        level.parse().map(Some)
+    }
+}
+
+impl Filter {
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let max = self
+            .targets
+            .iter()
+            .rev()
+            .find(|(name, _)| matches_target(target, name))
+            .map_or(self.default, |(_, level)| *level);
+
+        max.is_some_and(|max| level <= max)
+    }
+}
+
+fn matches_target(target: &str, name: &str) -> bool {
+    target == name
+        || target
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with("::"))
+        || target
+            .strip_suffix(name)
+            .is_some_and(|rest| rest.ends_with("::"))
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+/// Sets the filter from a spec in the same format as `AOC_LOG`. Has no effect
+/// once anything has been logged.
+pub fn init(spec: &str) -> Result<(), String> {
+    let filter = spec.parse()?;
+    let _ = FILTER.set(filter);
+
+    Ok(())
+}
+
+fn filter() -> &'static Filter {
+    FILTER.get_or_init(|| {
+        let spec = env::var(ENV_VAR).unwrap_or_default();
+        spec.parse().unwrap_or_else(|e| {
+            eprintln!("Ignoring {ENV_VAR}: {e}");
+            Filter::default()
+        })
+    })
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    filter().enabled(target, level)
+}
+
+static SINK: OnceLock<Mutex<Sender<String>>> = OnceLock::new();
+
+/// Sends every line logged from now on to `sink` instead of stderr, for a
+/// full-screen UI that owns the terminal. Has no effect when called again.
+pub fn redirect(sink: Sender<String>) {
+    let _ = SINK.set(Mutex::new(sink));
+}
+
+pub fn write(target: &str, level: Level, args: Arguments) {
+    let target = target.rsplit("::").next().unwrap_or(target);
+    let line = format!("[{level:<5} {target}] {args}");
+
+    match SINK.get() {
+        Some(sink) => {
+            let _ = sink.lock().map(|sink| sink.send(line));
+        }
+        None => eprintln!("{line}"),
+    }
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled(module_path!(), $level) {
+            $crate::log::write(module_path!(), $level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Filter, Level};
+
+    const DAY5: &str = "advent_of_code::y2024::day05";
+
+    #[test]
+    fn warn_by_default() {
+        let filter: Filter = "".parse().unwrap();
+
+        assert!(filter.enabled(DAY5, Level::Warn));
+        assert!(!filter.enabled(DAY5, Level::Info));
+        assert!(filter.enabled("advent_of_code::cache", Level::Warn));
+        assert!(!"day05=debug"
+            .parse::<Filter>()
+            .unwrap()
+            .enabled(DAY5, Level::Trace));
+        assert!(!"off".parse::<Filter>().unwrap().enabled(DAY5, Level::Error));
+    }
+
+    #[test]
+    fn default_level() {
+        let filter: Filter = "info".parse().unwrap();
+
+        assert!(filter.enabled(DAY5, Level::Info));
+        assert!(filter.enabled("advent_of_code", Level::Warn));
+        assert!(!filter.enabled(DAY5, Level::Debug));
+    }
+
+    #[test]
+    fn per_target_levels() {
+        let filter: Filter = "warn,day05=trace,advent_of_code::y2024::day06=off"
+            .parse()
+            .unwrap();
+
+        assert!(filter.enabled(DAY5, Level::Trace));
+        assert!(!filter.enabled("advent_of_code::y2024::day06", Level::Error));
+        assert!(filter.enabled("advent_of_code::y2024::day04", Level::Warn));
+        assert!(!filter.enabled("advent_of_code::y2024::day15", Level::Info));
+    }
+
+    #[test]
+    fn reject_unknown_level() {
+        assert!("day5=loud".parse::<Filter>().is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! Reads capital letters drawn with `#` and `.`, for puzzles whose answer is
+//! text rendered on a screen or a map. Knows the two fonts the puzzles use:
+//! letters 4 wide and 6 tall, and letters 6 wide and 10 tall.
+
+/// Letters 6 rows tall, usually 4 columns wide with a blank column between
+/// them.
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters 10 rows tall and 6 columns wide, with two blank columns between
+/// them.
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters drawn in `art`, where `#` (or `█`) is a lit pixel and
+/// anything else is dark.
+pub fn recognise(art: &str) -> Result<String, String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+
+    recognise_grid(&rows)
+}
+
+/// Reads the letters drawn in a grid of pixels, given row by row. Rows may
+/// have different lengths; missing pixels are dark.
+pub fn recognise_grid<R: AsRef<[bool]>>(rows: &[R]) -> Result<String, String> {
+    let lit = |row: usize, col: usize| rows[row].as_ref().get(col).copied().unwrap_or(false);
+
+    let blank_row = |row: &R| !row.as_ref().contains(&true);
+    let Some(top) = rows.iter().position(|row| !blank_row(row)) else {
+        return Err("Nothing is drawn".to_string());
+    };
+    let bottom = rows.iter().rposition(|row| !blank_row(row)).unwrap_or(top);
+
+    let height = bottom - top + 1;
+    let font = match height {
+        6 => SMALL,
+        10 => LARGE,
+        _ => return Err(format!("No font is {height} rows tall")),
+    };
+
+    let width = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+    let blank_col = |col: usize| (top..=bottom).all(|row| !lit(row, col));
+
+    // The letters are separated by blank columns, and none of them has a blank
+    // column of its own.
+    let mut letters = String::new();
+    let mut col = 0;
+    while col < width {
+        if blank_col(col) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < width && !blank_col(col) {
+            col += 1;
+        }
+
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|row| {
+                (start..col)
+                    .map(|col| if lit(row, col) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("\n");
+
+        let letter = font
+            .iter()
+            .find(|(_, pixels)| *pixels == glyph)
+            .map(|(letter, _)| *letter)
+            .ok_or(format!(
+                "Unknown letter at column {start}:\n{}",
+                glyph.replace('.', " ")
+            ))?;
+        letters.push(letter);
+    }
+
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{recognise, recognise_grid, LARGE, SMALL};
+
+    /// Draws the letters side by side, `gap` columns apart.
+    fn draw(font: &[(char, &str)], text: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                let (_, pixels) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+                pixels.lines().collect()
+            })
+            .collect();
+
+        (0..glyphs[0].len())
+            .map(|row| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[row])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn every_small_letter() {
+        let letters: String = SMALL.iter().map(|(letter, _)| letter).collect();
+
+        assert_eq!(recognise(&draw(SMALL, &letters, 1)), Ok(letters));
+    }
+
+    #[test]
+    fn every_large_letter() {
+        let letters: String = LARGE.iter().map(|(letter, _)| letter).collect();
+
+        assert_eq!(recognise(&draw(LARGE, &letters, 2)), Ok(letters));
+    }
+
+    #[test]
+    fn screen_with_padding() {
+        // Letters in fixed 5 column cells, as lit on a screen.
+        let art = "
+.##..###..####.#..#.
+#..#.#..#.#....#..#.
+#..#.###..###..####.
+####.#..#.#....#..#.
+#..#.#..#.#....#..#.
+#..#.###..####.#..#.
+";
+
+        assert_eq!(recognise(art), Ok("ABEH".to_string()));
+    }
+
+    #[test]
+    fn grid_of_pixels() {
+        let rows: Vec<Vec<bool>> = draw(SMALL, "JZ", 1)
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect();
+
+        assert_eq!(recognise_grid(&rows), Ok("JZ".to_string()));
+    }
+
+    #[test]
+    fn reject_unknown() {
+        assert!(recognise("").is_err());
+        assert!(recognise("#\n#\n#").is_err());
+        assert!(recognise("####\n####\n####\n####\n####\n####").is_err());
+    }
+}
@@ -0,0 +1,515 @@
+//! Timing history kept across commits. Every recorded run is appended to
+//! [`HISTORY_PATH`] as one JSON line, keyed by the git commit and the machine
+//! it ran on, so that later runs can be compared against a baseline measured
+//! on the same hardware.
+
+use std::{
+    env,
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    json,
+    runner::{self, Options, Stats},
+    Puzzle,
+};
+
+pub const HISTORY_PATH: &str = "perf-history.ndjson";
+
+/// Overrides the machine name, e.g. to share a baseline between hosts.
+pub const MACHINE_ENV: &str = "AOC_MACHINE";
+
+/// Slowdowns smaller than this are ignored even when significant.
+pub const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// The timings of one phase of a day.
+#[derive(Debug, PartialEq)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    /// `parse`, `part1` or `part2`.
+    pub phase: String,
+    pub time: Stats,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub commit: String,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    pub machine: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub results: Vec<Measurement>,
+}
+
+impl Entry {
+    /// Describes the current checkout and machine.
+    pub fn current(results: Vec<Measurement>) -> Self {
+        let (commit, dirty) = commit();
+
+        Self {
+            commit,
+            dirty,
+            machine: machine(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs()),
+            results,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let results: Vec<Value> = self
+            .results
+            .iter()
+            .map(|measurement| {
+                json!({
+                    "year": measurement.year,
+                    "day": measurement.day,
+                    "phase": measurement.phase,
+                    "time": json::stats(&measurement.time),
+                })
+            })
+            .collect();
+
+        json!({
+            "commit": self.commit,
+            "dirty": self.dirty,
+            "machine": self.machine,
+            "timestamp": self.timestamp,
+            "results": results,
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let results = value["results"]
+            .as_array()?
+            .iter()
+            .map(|result| {
+                Some(Measurement {
+                    year: result["year"].as_u64()? as u32,
+                    day: result["day"].as_u64()? as u32,
+                    phase: result["phase"].as_str()?.to_string(),
+                    time: json::parse_stats(&result["time"])?,
+                })
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            commit: value["commit"].as_str()?.to_string(),
+            dirty: value["dirty"].as_bool()?,
+            machine: value["machine"].as_str()?.to_string(),
+            timestamp: value["timestamp"].as_u64()?,
+            results,
+        })
+    }
+
+    /// The commit, marked when measured with uncommitted changes.
+    pub fn revision(&self) -> String {
+        if self.dirty {
+            format!("{}-dirty", self.commit)
+        } else {
+            self.commit.clone()
+        }
+    }
+}
+
+/// Loads every entry in the history. A missing file is an empty history.
+pub fn load(path: &str) -> io::Result<Vec<Entry>> {
+    let history = match fs::read_to_string(path) {
+        Ok(history) => history,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .ok()
+                .and_then(|value| Entry::from_json(&value))
+                .ok_or(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: invalid entry", i + 1),
+                ))
+        })
+        .collect()
+}
+
+pub fn append(path: &str, entry: &Entry) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    writeln!(file, "{}", entry.to_json())
+}
+
+/// The most recent entry that measured the year on the machine, optionally
+/// limited to commits that start with `commit`.
+pub fn baseline<'a>(
+    history: &'a [Entry],
+    year: u32,
+    machine: &str,
+    commit: Option<&str>,
+) -> Option<&'a Entry> {
+    history
+        .iter()
+        .rev()
+        .filter(|entry| entry.machine == machine)
+        .filter(|entry| entry.results.iter().any(|result| result.year == year))
+        .find(|entry| commit.is_none_or(|commit| entry.commit.starts_with(commit)))
+}
+
+/// Solves each day one after the other, so that they do not compete for the
+/// CPU, and collects the timings of every phase that succeeded.
+pub fn measure(puzzles: &[Puzzle], options: &Options) -> Vec<Measurement> {
+    let mut results = Vec::new();
+
+    for puzzle in puzzles {
+        let input = if options.example {
+            runner::load_input(puzzle.year, puzzle.day, true).0
+        } else {
+            match crate::get_cached_input(puzzle.year, puzzle.day) {
+                Some(input) => input,
+                None => {
+                    crate::warn!(
+                        "Skipping day {} of {}: no cached input",
+                        puzzle.day,
+                        puzzle.year
+                    );
+                    continue;
+                }
+            }
+        };
+
+        let report = puzzle.solve(&input, options);
+
+        if let Some(parse) = report.parse {
+            results.push(Measurement {
+                year: puzzle.year,
+                day: puzzle.day,
+                phase: "parse".to_string(),
+                time: parse,
+            });
+        }
+        for part in report.parts {
+            if part.answer.is_ok() {
+                results.push(Measurement {
+                    year: puzzle.year,
+                    day: puzzle.day,
+                    phase: format!("part{}", part.part),
+                    time: part.time,
+                });
+            }
+        }
+    }
+
+    results
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Slower,
+    Faster,
+    Unchanged,
+    /// Not in the baseline.
+    New,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Slower => "SLOWER",
+            Verdict::Faster => "faster",
+            Verdict::Unchanged => "~",
+            Verdict::New => "new",
+        };
+
+        f.pad(verdict)
+    }
+}
+
+pub struct Comparison {
+    pub year: u32,
+    pub day: u32,
+    pub phase: String,
+    pub baseline: Option<Duration>,
+    pub current: Duration,
+    /// Change of the mean in percent.
+    pub change: Option<f64>,
+    pub verdict: Verdict,
+}
+
+/// Compares every current measurement against the baseline. A change only
+/// counts when it exceeds `threshold` percent and a one-sided Welch's t-test
+/// finds it significant at the 5% level, which needs at least two runs on
+/// both sides.
+pub fn compare(
+    baseline: &[Measurement],
+    current: &[Measurement],
+    threshold: f64,
+) -> Vec<Comparison> {
+    current
+        .iter()
+        .map(|measurement| {
+            let Some(before) = baseline.iter().find(|b| {
+                (b.year, b.day, &b.phase) == (measurement.year, measurement.day, &measurement.phase)
+            }) else {
+                return Comparison {
+                    year: measurement.year,
+                    day: measurement.day,
+                    phase: measurement.phase.clone(),
+                    baseline: None,
+                    current: measurement.time.mean,
+                    change: None,
+                    verdict: Verdict::New,
+                };
+            };
+
+            let (old, new) = (&before.time, &measurement.time);
+            let change = (new.mean.as_secs_f64() / old.mean.as_secs_f64() - 1.0) * 100.0;
+            let verdict = match welch_t(old, new) {
+                Some((t, df)) if change > threshold && t > t_critical(df) => Verdict::Slower,
+                Some((t, df)) if change < -threshold && -t > t_critical(df) => Verdict::Faster,
+                _ => Verdict::Unchanged,
+            };
+
+            Comparison {
+                year: measurement.year,
+                day: measurement.day,
+                phase: measurement.phase.clone(),
+                baseline: Some(old.mean),
+                current: new.mean,
+                change: Some(change),
+                verdict,
+            }
+        })
+        .collect()
+}
+
+/// Welch's t statistic for `new` being slower than `old`, with its
+/// Welch–Satterthwaite degrees of freedom.
+fn welch_t(old: &Stats, new: &Stats) -> Option<(f64, f64)> {
+    if old.runs < 2 || new.runs < 2 {
+        return None;
+    }
+
+    let var = |stats: &Stats| stats.std_dev.as_secs_f64().powi(2) / stats.runs as f64;
+    let (old_var, new_var) = (var(old), var(new));
+    let diff = new.mean.as_secs_f64() - old.mean.as_secs_f64();
+
+    let se = (old_var + new_var).sqrt();
+    if se == 0.0 {
+        // Identical runs every time, any difference is real.
+        return Some((diff.signum() * f64::INFINITY, f64::INFINITY));
+    }
+
+    let df = (old_var + new_var).powi(2)
+        / (old_var.powi(2) / (old.runs - 1) as f64 + new_var.powi(2) / (new.runs - 1) as f64);
+
+    Some((diff / se, df))
+}
+
+/// One-sided critical value of Student's t distribution at the 5% level,
+/// rounded down to the nearest tabulated degrees of freedom.
+fn t_critical(df: f64) -> f64 {
+    const TABLE: [(f64, f64); 16] = [
+        (1.0, 6.314),
+        (2.0, 2.920),
+        (3.0, 2.353),
+        (4.0, 2.132),
+        (5.0, 2.015),
+        (6.0, 1.943),
+        (7.0, 1.895),
+        (8.0, 1.860),
+        (9.0, 1.833),
+        (10.0, 1.812),
+        (12.0, 1.782),
+        (15.0, 1.753),
+        (20.0, 1.725),
+        (30.0, 1.697),
+        (60.0, 1.671),
+        (120.0, 1.658),
+    ];
+
+    if df > 120.0 {
+        return 1.645;
+    }
+
+    TABLE
+        .iter()
+        .rev()
+        .find(|(table_df, _)| df >= *table_df)
+        .map_or(TABLE[0].1, |(_, t)| *t)
+}
+
+/// The current commit and whether the working tree has changes, or `unknown`
+/// outside a git checkout.
+pub fn commit() -> (String, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short=12", "HEAD"]) {
+        Some(commit) => {
+            let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty());
+
+            (commit, dirty)
+        }
+        None => ("unknown".to_string(), false),
+    }
+}
+
+/// Identifies the machine by host name, OS and architecture.
+pub fn machine() -> String {
+    if let Ok(machine) = env::var(MACHINE_ENV) {
+        return machine;
+    }
+
+    let host = fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or("unknown".to_string());
+
+    format!("{host}-{}-{}", env::consts::OS, env::consts::ARCH)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::runner::Stats;
+
+    use super::{baseline, compare, t_critical, Entry, Measurement, Verdict};
+
+    fn measurement(phase: &str, millis: &[u64]) -> Measurement {
+        Measurement {
+            year: 2024,
+            day: 6,
+            phase: phase.to_string(),
+            time: Stats::from_samples(millis.iter().map(|ms| Duration::from_millis(*ms)).collect()),
+        }
+    }
+
+    fn entry(commit: &str, machine: &str) -> Entry {
+        Entry {
+            commit: commit.to_string(),
+            dirty: false,
+            machine: machine.to_string(),
+            timestamp: 0,
+            results: vec![measurement("part1", &[1, 2])],
+        }
+    }
+
+    #[test]
+    fn flag_significant_slowdown() {
+        let old = [measurement("part1", &[10, 11, 10, 9, 10])];
+        let new = [measurement("part1", &[15, 16, 14, 15, 15])];
+
+        let comparisons = compare(&old, &new, 5.0);
+
+        assert_eq!(comparisons[0].verdict, Verdict::Slower);
+        assert!((comparisons[0].change.unwrap() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ignore_noise() {
+        let old = [measurement("part1", &[10, 20, 5, 15, 10])];
+        let new = [measurement("part1", &[12, 22, 6, 14, 11])];
+
+        assert_eq!(compare(&old, &new, 5.0)[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn ignore_change_below_threshold() {
+        let old = [measurement("part1", &[100, 100, 100])];
+        let new = [measurement("part1", &[103, 103, 103])];
+
+        assert_eq!(compare(&old, &new, 5.0)[0].verdict, Verdict::Unchanged);
+        assert_eq!(compare(&old, &new, 1.0)[0].verdict, Verdict::Slower);
+    }
+
+    #[test]
+    fn detect_speedup_and_new_phase() {
+        let old = [measurement("part1", &[20, 21, 20])];
+        let new = [
+            measurement("part1", &[10, 11, 10]),
+            measurement("part2", &[5, 5, 5]),
+        ];
+
+        let comparisons = compare(&old, &new, 5.0);
+
+        assert_eq!(comparisons[0].verdict, Verdict::Faster);
+        assert_eq!(comparisons[1].verdict, Verdict::New);
+    }
+
+    #[test]
+    fn single_run_is_never_significant() {
+        let old = [measurement("part1", &[10])];
+        let new = [measurement("part1", &[20])];
+
+        assert_eq!(compare(&old, &new, 5.0)[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn critical_values() {
+        assert_eq!(t_critical(0.5), 6.314);
+        assert_eq!(t_critical(11.7), 1.812);
+        assert_eq!(t_critical(1000.0), 1.645);
+    }
+
+    #[test]
+    fn baseline_for_machine_and_commit() {
+        let history = [
+            entry("aaa111", "box"),
+            entry("bbb222", "laptop"),
+            entry("ccc333", "box"),
+        ];
+
+        assert_eq!(
+            baseline(&history, 2024, "box", None).unwrap().commit,
+            "ccc333"
+        );
+        assert_eq!(
+            baseline(&history, 2024, "box", Some("aaa")).unwrap().commit,
+            "aaa111"
+        );
+        assert!(baseline(&history, 2024, "box", Some("bbb")).is_none());
+    }
+
+    #[test]
+    fn baseline_for_year() {
+        let mut later = entry("ddd444", "box");
+        later.results[0].year = 2025;
+        let history = [entry("aaa111", "box"), later];
+
+        assert_eq!(
+            baseline(&history, 2024, "box", None).unwrap().commit,
+            "aaa111"
+        );
+        assert!(baseline(&history, 2023, "box", None).is_none());
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        let expected = entry("abc", "box");
+
+        assert_eq!(Entry::from_json(&expected.to_json()), Some(expected));
+    }
+}
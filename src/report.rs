@@ -0,0 +1,484 @@
+//! A self-contained HTML report of the year: every day's answers and their
+//! verification status, charts of the timings and allocations, and drawings
+//! of the solutions that have one. Needs nothing but a browser to read.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    fs,
+    time::Duration,
+};
+
+use crate::{
+    alloc,
+    answers::{Answers, Key, Status},
+    examples, perf,
+    runner::{self, PartReport, Report},
+    Answer, Puzzle,
+};
+
+pub const REPORT_PATH: &str = "report.html";
+
+/// Shown instead of the answers when they are masked.
+const MASK: &str = "••••••";
+
+/// A day's solution drawn as a grid of characters with some cells marked,
+/// e.g. the cells a guard walks through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Visualisation {
+    pub caption: String,
+    pub rows: Vec<String>,
+    /// Row and column of each marked cell.
+    pub marked: BTreeSet<(usize, usize)>,
+}
+
+/// Draws the solutions of the puzzles that can be drawn. The drawings use the
+/// day's default example rather than the real input, which is not ours to
+/// publish. A drawing that panics is kept as the failure, so that the rest of
+/// the report is still written.
+pub fn visualise(puzzles: &[Puzzle]) -> BTreeMap<u32, Result<Visualisation, String>> {
+    puzzles
+        .iter()
+        .filter_map(|puzzle| {
+            let path = examples::path(puzzle.year, puzzle.day, examples::DEFAULT_EXAMPLE);
+            let input = fs::read_to_string(path).ok()?;
+
+            match runner::catch_panic(|| puzzle.visualise(&input)) {
+                Ok(visualisation) => Some((puzzle.day, Ok(visualisation?))),
+                Err(failure) => Some((puzzle.day, Err(failure.to_string()))),
+            }
+        })
+        .collect()
+}
+
+/// Renders the report of every day's results for the year. Days that could
+/// not be run are listed with their error.
+pub fn render(
+    year: u32,
+    results: &[(u32, Result<Report, String>)],
+    answers: &Answers,
+    visualisations: &BTreeMap<u32, Result<Visualisation, String>>,
+    mask: bool,
+) -> String {
+    let mut html = String::new();
+    let title = format!("Advent of Code {year}");
+
+    let answered = results
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().ok())
+        .flat_map(|report| &report.parts)
+        .filter(|part| part.answer.is_ok())
+        .count();
+    let verified = results
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().ok())
+        .flat_map(|report| report.parts.iter().map(move |part| (report.day, part)))
+        .filter(|(day, part)| status(year, *day, part, answers, mask).0 == "correct")
+        .count();
+    let (commit, dirty) = perf::commit();
+    let revision = if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    };
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p>{verified} of {answered} answers verified, at {}</p>\n",
+        escape(&revision)
+    );
+
+    html.push_str("<h2>Answers</h2>\n<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Status</th><th>Median time</th><th>Allocated</th></tr>\n");
+    for (day, result) in results {
+        match result {
+            Ok(report) => {
+                for part in &report.parts {
+                    let answer = match (&part.answer, mask) {
+                        (Ok(_), true) => MASK.to_string(),
+                        (Ok(answer), false) => answer_html(answer),
+                        (Err(_), _) => "-".to_string(),
+                    };
+                    let (class, status) = status(year, *day, part, answers, mask);
+                    let allocated = part
+                        .alloc
+                        .map_or("-".to_string(), |usage| alloc::format_bytes(usage.bytes));
+
+                    let _ = writeln!(
+                        html,
+                        "<tr><td>{day}</td><td>{}</td><td>{answer}</td>\
+                         <td class=\"{class}\">{}</td><td>{:.2?}</td><td>{allocated}</td></tr>",
+                        part.part,
+                        escape(&status),
+                        part.time.median
+                    );
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{day}</td><td></td><td></td><td class=\"failed\" colspan=\"3\">{}</td></tr>",
+                    escape(e)
+                );
+            }
+        }
+    }
+    html.push_str("</table>\n");
+
+    let parts: Vec<(String, &PartReport)> = results
+        .iter()
+        .filter_map(|(day, result)| Some((*day, result.as_ref().ok()?)))
+        .flat_map(|(day, report)| {
+            report
+                .parts
+                .iter()
+                .map(move |part| (format!("day {day} part {}", part.part), part))
+        })
+        .collect();
+
+    let timings: Vec<_> = parts
+        .iter()
+        .map(|(label, part)| {
+            let median = part.time.median;
+            (label.clone(), nanos(median), format!("{median:.2?}"))
+        })
+        .collect();
+    if !timings.is_empty() {
+        html.push_str("<h2>Timings</h2>\n<p>Median time of each part, on a log scale.</p>\n");
+        html.push_str(&bar_chart(&timings, true));
+    }
+
+    let allocations: Vec<_> = parts
+        .iter()
+        .filter_map(|(label, part)| {
+            let usage = part.alloc?;
+            Some((
+                label.clone(),
+                usage.bytes as f64,
+                alloc::format_bytes(usage.bytes),
+            ))
+        })
+        .collect();
+    if !allocations.is_empty() {
+        html.push_str("<h2>Allocations</h2>\n<p>Bytes allocated by each part.</p>\n");
+        html.push_str(&bar_chart(&allocations, false));
+    }
+
+    if !visualisations.is_empty() {
+        html.push_str("<h2>Visualisations</h2>\n");
+        for (day, visualisation) in visualisations {
+            match visualisation {
+                Ok(visualisation) => html.push_str(&figure(*day, visualisation)),
+                Err(e) => {
+                    let _ = writeln!(
+                        html,
+                        "<p class=\"failed\">Day {day}: visualisation failed: {}</p>",
+                        escape(e)
+                    );
+                }
+            }
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// The CSS class and text of a part's verification status. Masking leaves
+/// out the expected answer of a mismatch.
+fn status(
+    year: u32,
+    day: u32,
+    part: &PartReport,
+    answers: &Answers,
+    mask: bool,
+) -> (&'static str, String) {
+    let answer = match &part.answer {
+        Ok(answer) => answer,
+        Err(failure) => return ("failed", failure.to_string()),
+    };
+
+    let key = Key {
+        year,
+        day,
+        part: part.part,
+    };
+    match answers.check(key, answer) {
+        Status::Correct => ("correct", "ok".to_string()),
+        Status::Incorrect { .. } if mask => ("incorrect", "MISMATCH".to_string()),
+        status @ Status::Incorrect { .. } => ("incorrect", status.to_string()),
+        Status::Unknown => ("unknown", "unverified".to_string()),
+    }
+}
+
+/// An answer as HTML. Letters drawn over several lines are shown as read,
+/// with the drawing below them.
+fn answer_html(answer: &Answer) -> String {
+    let letters = answer.letters();
+    if !answer.is_multiline() {
+        return escape(&answer.to_string());
+    }
+
+    let drawing = format!("<pre>{}</pre>", escape(&answer.to_string()));
+    if letters.is_multiline() {
+        drawing
+    } else {
+        format!("{}{drawing}", escape(&letters.to_string()))
+    }
+}
+
+const BAR_HEIGHT: usize = 18;
+const LABEL_WIDTH: usize = 110;
+const BAR_WIDTH: f64 = 420.0;
+
+/// A horizontal SVG bar chart of labelled values, each bar annotated with
+/// its formatted value.
+fn bar_chart(bars: &[(String, f64, String)], log_scale: bool) -> String {
+    let scale = |value: f64| {
+        if log_scale {
+            (value.max(1.0)).log10()
+        } else {
+            value
+        }
+    };
+    let floor = if log_scale {
+        bars.iter()
+            .map(|(_, value, _)| scale(*value))
+            .fold(f64::INFINITY, f64::min)
+            .floor()
+    } else {
+        0.0
+    };
+    let max = bars
+        .iter()
+        .map(|(_, value, _)| scale(*value) - floor)
+        .fold(0.0, f64::max);
+
+    let height = bars.len() * (BAR_HEIGHT + 4);
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{height}\" role=\"img\">\n",
+        LABEL_WIDTH + BAR_WIDTH as usize + 100
+    );
+    for (i, (label, value, text)) in bars.iter().enumerate() {
+        let y = i * (BAR_HEIGHT + 4);
+        let fraction = if max > 0.0 {
+            (scale(*value) - floor) / max
+        } else {
+            0.0
+        };
+        let width = (fraction * BAR_WIDTH).max(1.0);
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\">{}</text><rect x=\"{LABEL_WIDTH}\" y=\"{y}\" width=\"{width:.1}\" height=\"{BAR_HEIGHT}\"/><text x=\"{:.1}\" y=\"{}\">{}</text>",
+            y + BAR_HEIGHT - 4,
+            escape(label),
+            LABEL_WIDTH as f64 + width + 6.0,
+            y + BAR_HEIGHT - 4,
+            escape(text)
+        );
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+fn figure(day: u32, visualisation: &Visualisation) -> String {
+    let mut pre = String::new();
+    for (r, row) in visualisation.rows.iter().enumerate() {
+        for (c, ch) in row.chars().enumerate() {
+            let ch = escape(&ch.to_string());
+            if visualisation.marked.contains(&(r, c)) {
+                let _ = write!(pre, "<mark>{ch}</mark>");
+            } else {
+                pre.push_str(&ch);
+            }
+        }
+        pre.push('\n');
+    }
+
+    format!(
+        "<figure>\n<pre>{pre}</pre>\n<figcaption>Day {day}: {}</figcaption>\n</figure>\n",
+        escape(&visualisation.caption)
+    )
+}
+
+fn nanos(duration: Duration) -> f64 {
+    duration.as_nanos() as f64
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 60em; padding: 0 1em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; text-align: left; border-bottom: 1px solid #ddd; vertical-align: top; }
+td pre { margin: 0.2em 0 0; font-size: 0.6em; line-height: 1; }
+.correct { color: #17803d; }
+.incorrect, .failed { color: #c0262d; }
+.unknown { color: #a46a00; }
+svg { font-size: 12px; display: block; margin: 1em 0; }
+rect { fill: #4a72b8; }
+figure { display: inline-block; margin: 0 2em 1em 0; vertical-align: top; }
+figure pre { line-height: 1.1; }
+mark { background: #f5d547; }
+";
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        time::Duration,
+    };
+
+    use super::{escape, render, visualise, Visualisation};
+    use crate::{
+        answers::{Answers, Key},
+        runner::{Failure, PartReport, Report, Stats},
+        y2024, Answer, Puzzle, Solution,
+    };
+
+    fn part(part: u32, answer: Result<Answer, Failure>) -> PartReport {
+        PartReport {
+            part,
+            answer,
+            time: Stats::from_samples(vec![Duration::from_micros(40)]),
+            alloc: None,
+        }
+    }
+
+    fn results() -> Vec<(u32, Result<Report, String>)> {
+        let report = Report {
+            year: 2024,
+            day: 4,
+            load: Duration::ZERO,
+            parse: None,
+            parse_alloc: None,
+            parts: vec![
+                part(1, Ok(Answer::from(2468))),
+                part(2, Err(Failure::OutOfMemory)),
+            ],
+            cached: false,
+        };
+
+        vec![(4, Ok(report)), (5, Err("no cached input".to_string()))]
+    }
+
+    fn answers() -> Answers {
+        let mut answers = Answers::default();
+        answers
+            .insert(
+                Key {
+                    year: 2024,
+                    day: 4,
+                    part: 1,
+                },
+                2468,
+            )
+            .unwrap();
+
+        answers
+    }
+
+    #[test]
+    fn answers_and_status() {
+        let html = render(2024, &results(), &answers(), &BTreeMap::new(), false);
+
+        assert!(html.contains("<td>2468</td>"));
+        assert!(html.contains("<td class=\"correct\">ok</td>"));
+        assert!(html.contains("<td class=\"failed\">OUT OF MEMORY</td>"));
+        assert!(html.contains("no cached input"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("Allocations"));
+    }
+
+    #[test]
+    fn masked_answers() {
+        let html = render(2024, &results(), &answers(), &BTreeMap::new(), true);
+
+        assert!(!html.contains("2468"));
+        assert!(html.contains("<td class=\"correct\">ok</td>"));
+    }
+
+    #[test]
+    fn marked_cells() {
+        let visualisation = Visualisation {
+            caption: "path".to_string(),
+            rows: vec!["#<".to_string(), ".X".to_string()],
+            marked: BTreeSet::from([(1, 1)]),
+        };
+        let html = render(
+            2024,
+            &[],
+            &Answers::default(),
+            &BTreeMap::from([(6, Ok(visualisation))]),
+            false,
+        );
+
+        assert!(html.contains("<pre>#&lt;\n.<mark>X</mark>\n</pre>"));
+        assert!(html.contains("Day 6: path"));
+    }
+
+    #[test]
+    fn visualise_examples() {
+        let visualisations = visualise(y2024::ALL);
+
+        assert_eq!(visualisations[&4].as_ref().unwrap().marked.len(), 54);
+        assert_eq!(visualisations[&6].as_ref().unwrap().marked.len(), 41);
+    }
+
+    struct Unguarded;
+
+    impl Solution for Unguarded {
+        const YEAR: u32 = 2024;
+        const DAY: u32 = 6;
+        type Parsed = ();
+
+        fn parse(_input: &str) {}
+
+        fn part1(_parsed: &()) -> impl Into<Answer> {
+            0
+        }
+
+        fn part2(_parsed: &()) -> impl Into<Answer> {
+            0
+        }
+
+        fn visualise(_parsed: &()) -> Option<Visualisation> {
+            panic!("No guard on map");
+        }
+    }
+
+    #[test]
+    fn report_failed_visualisation() {
+        let visualisations = visualise(&[Puzzle::new::<Unguarded>()]);
+        let html = render(2024, &[], &Answers::default(), &visualisations, false);
+
+        assert!(visualisations[&6].as_ref().is_err());
+        assert!(html.contains("Day 6: visualisation failed: PANICKED at"));
+        assert!(html.contains("No guard on map"));
+    }
+
+    #[test]
+    fn escape_html() {
+        assert_eq!(
+            escape("<a href=\"x\">&'"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&#39;"
+        );
+    }
+}
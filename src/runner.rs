@@ -0,0 +1,827 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    env,
+    fmt::Display,
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    process,
+    str::FromStr,
+    sync::{Mutex, Once, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, Usage},
+    answers::{Answers, Key, Status, ANSWERS_PATH},
+    cache, get_cached_input, get_input, get_test_input, isolate, json, Answer, Puzzle, Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Ndjson,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(format!("unknown format: {s}")),
+        }
+    }
+}
+
+pub struct Options {
+    pub repeat: usize,
+    pub format: Format,
+    /// Solve the example input instead of the real one.
+    pub example: bool,
+    /// Wall-clock limit for each part. Setting it or `memory` runs every part
+    /// in its own child process.
+    pub timeout: Option<Duration>,
+    /// Address space limit in bytes for each part.
+    pub memory: Option<u64>,
+    /// Solve only this part instead of both.
+    pub part: Option<u32>,
+    /// Solve again even when the answers are cached.
+    pub force: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            repeat: 1,
+            format: Format::Text,
+            example: false,
+            timeout: None,
+            memory: None,
+            part: None,
+            force: false,
+        }
+    }
+}
+
+impl Options {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-r" | "--repeat" => {
+                    let value = args.next().ok_or(format!("{arg} needs a value"))?;
+                    options.repeat = value
+                        .parse()
+                        .map_err(|_| format!("invalid repeat count: {value}"))?;
+
+                    if options.repeat == 0 {
+                        return Err("repeat count must be at least 1".to_string());
+                    }
+                }
+                "-f" | "--format" => {
+                    let value = args.next().ok_or(format!("{arg} needs a value"))?;
+                    options.format = value.parse()?;
+                }
+                "-e" | "--example" => options.example = true,
+                "-t" | "--timeout" => {
+                    let value = args.next().ok_or(format!("{arg} needs a value"))?;
+                    let timeout = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|seconds| *seconds > 0.0)
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                        .ok_or(format!("invalid timeout: {value}"))?;
+
+                    options.timeout = Some(timeout);
+                }
+                "-m" | "--memory" => {
+                    let value = args.next().ok_or(format!("{arg} needs a value"))?;
+                    let bytes = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|megabytes| *megabytes > 0)
+                        .and_then(|megabytes| megabytes.checked_mul(1024 * 1024))
+                        .ok_or(format!("invalid memory limit: {value}"))?;
+                    if cfg!(not(unix)) {
+                        return Err(format!("{arg} is only supported on unix"));
+                    }
+
+                    options.memory = Some(bytes);
+                }
+                "-p" | "--part" => {
+                    let value = args.next().ok_or(format!("{arg} needs a value"))?;
+                    options.part = match value.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err(format!("invalid part: {value}")),
+                    };
+                }
+                "--force" => options.force = true,
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+
+        Ok(options)
+    }
+
+    pub fn isolated(&self) -> bool {
+        self.timeout.is_some() || self.memory.is_some()
+    }
+
+    /// Whether cached answers may be reused. Timing several runs or counting
+    /// allocations is about measuring, so those always solve.
+    pub fn cached(&self) -> bool {
+        !self.force && self.repeat == 1 && !alloc::enabled()
+    }
+
+    /// The parts to solve.
+    pub fn parts(&self) -> &'static [u32] {
+        match self.part {
+            Some(1) => &[1],
+            Some(2) => &[2],
+            _ => &[1, 2],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Sample standard deviation, zero for a single run.
+    pub std_dev: Duration,
+    pub runs: usize,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "No samples to summarise");
+        samples.sort();
+
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / len as u32;
+        let std_dev = if len > 1 {
+            let mean = mean.as_nanos() as f64;
+            let variance = samples
+                .iter()
+                .map(|sample| (sample.as_nanos() as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (len - 1) as f64;
+
+            Duration::from_nanos(variance.sqrt().round() as u64)
+        } else {
+            Duration::ZERO
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            mean,
+            std_dev,
+            runs: len,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}",
+            self.min, self.median, self.mean
+        )
+    }
+}
+
+pub struct Report {
+    pub year: u32,
+    pub day: u32,
+    pub load: Duration,
+    /// Missing when the input was never parsed, e.g. because every part was
+    /// solved in a child process that failed, or parsing panicked.
+    pub parse: Option<Stats>,
+    /// Only measured when built with the `count-allocations` feature.
+    pub parse_alloc: Option<Usage>,
+    pub parts: Vec<PartReport>,
+    /// Whether the answers and timings come from the [`Cache`](crate::cache::Cache).
+    pub cached: bool,
+}
+
+pub struct PartReport {
+    pub part: u32,
+    pub answer: Result<Answer, Failure>,
+    /// For a failed part, the time until it failed.
+    pub time: Stats,
+    pub alloc: Option<Usage>,
+}
+
+/// The result of one implementation of a part in compare mode.
+pub struct ImplementationReport {
+    pub part: u32,
+    /// `default` for the solution's own `part1` or `part2`.
+    pub name: &'static str,
+    pub answer: Result<Answer, Failure>,
+    pub time: Stats,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    Timeout(Duration),
+    OutOfMemory,
+    Crashed(String),
+    Panicked {
+        message: String,
+        /// Where in the source the panic happened, e.g. `src/y2024/day02.rs:12:5`.
+        location: Option<String>,
+    },
+}
+
+impl Failure {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Failure::Timeout(_) => "timeout",
+            Failure::OutOfMemory => "out_of_memory",
+            Failure::Crashed(_) => "crashed",
+            Failure::Panicked { .. } => "panicked",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Timeout(timeout) => f.pad(&format!("TIMEOUT after {timeout:.2?}")),
+            Failure::OutOfMemory => f.pad("OUT OF MEMORY"),
+            Failure::Crashed(message) => f.pad(&format!("CRASHED: {message}")),
+            Failure::Panicked {
+                message,
+                location: Some(location),
+            } => f.pad(&format!("PANICKED at {location}: {message}")),
+            Failure::Panicked {
+                message,
+                location: None,
+            } => f.pad(&format!("PANICKED: {message}")),
+        }
+    }
+}
+
+impl PartReport {
+    /// The verification status of the answer, or the failure if there is no
+    /// answer, for display.
+    pub fn status(&self, year: u32, day: u32, answers: &Answers) -> String {
+        match &self.answer {
+            Ok(answer) => {
+                let key = Key {
+                    year,
+                    day,
+                    part: self.part,
+                };
+
+                answers.check(key, answer).to_string()
+            }
+            Err(failure) => failure.to_string(),
+        }
+    }
+
+    /// Whether the part failed to run or its answer does not match the
+    /// recorded one.
+    pub fn failed(&self, year: u32, day: u32, answers: &Answers) -> bool {
+        match &self.answer {
+            Ok(answer) => {
+                let key = Key {
+                    year,
+                    day,
+                    part: self.part,
+                };
+
+                matches!(answers.check(key, answer), Status::Incorrect { .. })
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+/// Entry point for the day binaries. Reads the runner options from the
+/// command line, runs the solution and prints the report.
+pub fn main<S: Solution>() {
+    isolate::serve(&[Puzzle::new::<S>()]);
+
+    let options = match Options::from_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!(
+                "usage: y{}-day{} [--repeat N] [--format text|json|ndjson] [--example] [--part N] [--timeout SECS] [--memory MB] [--force]",
+                S::YEAR,
+                S::DAY
+            );
+            process::exit(2);
+        }
+    };
+
+    let answers = Answers::load(ANSWERS_PATH).unwrap_or_else(|e| {
+        eprintln!("Could not load {ANSWERS_PATH}: {e}");
+        Answers::default()
+    });
+
+    let report = run::<S>(&options);
+
+    print_report(&report, &answers, &options);
+
+    if report.parts.iter().any(|part| part.answer.is_err()) {
+        process::exit(1);
+    }
+}
+
+pub fn run<S: Solution>(options: &Options) -> Report {
+    Puzzle::new::<S>().run(options)
+}
+
+pub fn load_input(year: u32, day: u32, example: bool) -> (String, Duration) {
+    let start = Instant::now();
+    let mut input = String::new();
+    if example {
+        get_test_input(year, day).read_to_string(&mut input)
+    } else {
+        get_input(year, day).read_to_string(&mut input)
+    }
+    .expect("Could not read input");
+
+    (input, start.elapsed())
+}
+
+/// Parses an already loaded input and solves the given parts of it in this
+/// process. The load time of the returned report is left at zero.
+pub fn solve<S: Solution>(input: &str, parts: &[u32], options: &Options) -> Report {
+    let (parsed, parse, parse_alloc) =
+        match catch_panic(|| time(options.repeat, || S::parse(input))) {
+            Ok((parsed, parse, parse_alloc)) => (Ok(parsed), Some(parse), parse_alloc),
+            Err(failure) => (Err(failure), None, None),
+        };
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let solved = parsed.as_ref().map_err(Failure::clone).and_then(|parsed| {
+                catch_panic(|| {
+                    time(options.repeat, || match part {
+                        1 => S::part1(parsed).into(),
+                        2 => S::part2(parsed).into(),
+                        _ => panic!("Day {} has no part {part}", S::DAY),
+                    })
+                })
+            });
+
+            match solved {
+                Ok((answer, time, alloc)) => PartReport {
+                    part,
+                    answer: Ok(answer),
+                    time,
+                    alloc,
+                },
+                Err(failure) => PartReport {
+                    part,
+                    answer: Err(failure),
+                    time: Stats::from_samples(vec![start.elapsed()]),
+                    alloc: None,
+                },
+            }
+        })
+        .collect();
+
+    Report {
+        year: S::YEAR,
+        day: S::DAY,
+        load: Duration::ZERO,
+        parse,
+        parse_alloc,
+        parts,
+        cached: false,
+    }
+}
+
+/// Parses the input once and runs every implementation of the given parts
+/// on it.
+pub fn compare<S: Solution>(input: &str, options: &Options) -> Vec<ImplementationReport> {
+    let parsed = catch_panic(|| S::parse(input));
+    let alternatives = S::alternatives();
+
+    let mut reports = Vec::new();
+    for &part in options.parts() {
+        let default: fn(&S::Parsed) -> Answer = match part {
+            1 => |parsed| S::part1(parsed).into(),
+            _ => |parsed| S::part2(parsed).into(),
+        };
+        let implementations = [("default", default)].into_iter().chain(
+            alternatives
+                .iter()
+                .filter(|alternative| alternative.part == part)
+                .map(|alternative| (alternative.name, alternative.solve)),
+        );
+
+        for (name, solve) in implementations {
+            let start = Instant::now();
+            let solved = parsed
+                .as_ref()
+                .map_err(Failure::clone)
+                .and_then(|parsed| catch_panic(|| time(options.repeat, || solve(parsed))));
+
+            reports.push(match solved {
+                Ok((answer, time, _)) => ImplementationReport {
+                    part,
+                    name,
+                    answer: Ok(answer),
+                    time,
+                },
+                Err(failure) => ImplementationReport {
+                    part,
+                    name,
+                    answer: Err(failure),
+                    time: Stats::from_samples(vec![start.elapsed()]),
+                },
+            });
+        }
+    }
+
+    reports
+}
+
+thread_local! {
+    /// Set while running under [`catch_panic`], so that the panic hook keeps
+    /// the location instead of printing the panic.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a panic into a failure with its message and location.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Failure> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                PANIC_LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                default(info);
+            }
+        }));
+    });
+
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+
+    result.map_err(|payload| Failure::Panicked {
+        message: panic_message(&payload),
+        location: PANIC_LOCATION.take(),
+    })
+}
+
+/// Solves every puzzle on its cached input, each on its own thread. A day
+/// without a cached input or whose solution panics is reported as an error
+/// and does not affect the others. Cached answers are reused unless the
+/// options rule it out.
+pub fn run_all(puzzles: &[Puzzle], options: &Options) -> Vec<(u32, Result<Report, String>)> {
+    let cache = options.cached().then(|| Mutex::new(cache::load()));
+
+    // Each thread borrows the cache, which is saved once they are all done.
+    let shared = cache.as_ref();
+    let results = thread::scope(|scope| {
+        let handles: Vec<_> = puzzles
+            .iter()
+            .map(|puzzle| {
+                let handle = scope.spawn(move || {
+                    let (input, load) = if options.example {
+                        load_input(puzzle.year, puzzle.day, true)
+                    } else {
+                        let start = Instant::now();
+                        let input =
+                            get_cached_input(puzzle.year, puzzle.day).ok_or("no cached input")?;
+
+                        (input, start.elapsed())
+                    };
+
+                    let cached = shared.and_then(|cache| {
+                        let cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+                        cache.get(puzzle.year, puzzle.day, options.parts(), &input)
+                    });
+                    let mut report = cached.unwrap_or_else(|| {
+                        let report = puzzle.solve(&input, options);
+                        if let Some(cache) = shared {
+                            cache
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .insert(&report, &input);
+                        }
+
+                        report
+                    });
+                    report.load = load;
+
+                    Ok(report)
+                });
+
+                (puzzle.day, handle)
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|(day, handle)| {
+                let result = handle.join().unwrap_or_else(|payload| {
+                    Err(format!("panicked: {}", panic_message(&payload)))
+                });
+
+                (day, result)
+            })
+            .collect()
+    });
+
+    if let Some(cache) = cache {
+        cache::save(&cache.into_inner().unwrap_or_else(PoisonError::into_inner));
+    }
+
+    results
+}
+
+pub fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Prints a single day's report. As text the answers go to stdout and the
+/// timings and verification status to stderr, so the answers can be piped.
+pub fn print_report(report: &Report, answers: &Answers, options: &Options) {
+    if options.format != Format::Text {
+        json::print(&json::report_records(report, answers), options.format);
+        return;
+    }
+
+    for part in &report.parts {
+        if let Ok(answer) = &part.answer {
+            println!("{answer}");
+        }
+    }
+
+    if report.cached {
+        eprintln!("Day {} (cached, --force to solve again)", report.day);
+    } else {
+        eprintln!("Day {} ({} runs)", report.day, options.repeat);
+    }
+    eprintln!("  load    {:>10.2?}", report.load);
+    match &report.parse {
+        Some(parse) => eprintln!("  parse   {parse}"),
+        None => eprintln!("  parse   -"),
+    }
+    if let Some(usage) = &report.parse_alloc {
+        eprintln!("          {usage}");
+    }
+    for part in &report.parts {
+        eprintln!(
+            "  part {}  {}  {}",
+            part.part,
+            part.time,
+            part.status(report.year, report.day, answers)
+        );
+        if let Some(usage) = &part.alloc {
+            eprintln!("          {usage}");
+        }
+    }
+}
+
+/// Runs `f` `repeat` times and returns the result of the last run together
+/// with the timing statistics of all of them and the allocations of the first.
+fn time<T>(repeat: usize, mut f: impl FnMut() -> T) -> (T, Stats, Option<Usage>) {
+    let mut samples = Vec::with_capacity(repeat);
+    let mut result = None;
+    let mut usage = None;
+
+    for i in 0..repeat {
+        let start = Instant::now();
+        let value = if i == 0 {
+            let (value, first_usage) = alloc::measure(&mut f);
+            usage = first_usage;
+            value
+        } else {
+            f()
+        };
+        samples.push(start.elapsed());
+
+        result = Some(value);
+    }
+
+    (
+        result.expect("Ran at least once"),
+        Stats::from_samples(samples),
+        usage,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{alloc, Answer, Implementation, Solution};
+
+    use super::{compare, solve, Failure, Format, Options, Stats};
+
+    struct Panics;
+
+    impl Solution for Panics {
+        const YEAR: u32 = 2024;
+
+        const DAY: u32 = 99;
+
+        type Parsed = u32;
+
+        fn parse(input: &str) -> Self::Parsed {
+            input.trim().parse().unwrap()
+        }
+
+        fn part1(parsed: &Self::Parsed) -> impl Into<Answer> {
+            *parsed
+        }
+
+        fn part2(_: &Self::Parsed) -> impl Into<Answer> {
+            panic!("no part 2");
+            #[allow(unreachable_code)]
+            0
+        }
+
+        fn alternatives() -> Vec<Implementation<Self::Parsed>> {
+            vec![Implementation {
+                name: "doubled",
+                part: 1,
+                solve: |parsed| (parsed * 2).into(),
+            }]
+        }
+    }
+
+    fn panic_location(failure: &Failure) -> &str {
+        match failure {
+            Failure::Panicked {
+                location: Some(location),
+                ..
+            } => location,
+            _ => panic!("Expected a panic with a location, got {failure:?}"),
+        }
+    }
+
+    #[test]
+    fn stats_odd_samples() {
+        let samples = [5, 1, 3].map(Duration::from_millis).to_vec();
+        let expected = Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(3),
+            mean: Duration::from_millis(3),
+            std_dev: Duration::from_millis(2),
+            runs: 3,
+        };
+
+        assert_eq!(Stats::from_samples(samples), expected);
+    }
+
+    #[test]
+    fn stats_even_samples() {
+        let samples = [4, 1, 2, 9].map(Duration::from_millis).to_vec();
+        let expected = Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(3),
+            mean: Duration::from_millis(4),
+            // sqrt(38 / 3) ms
+            std_dev: Duration::from_nanos(3_559_026),
+            runs: 4,
+        };
+
+        assert_eq!(Stats::from_samples(samples), expected);
+    }
+
+    #[test]
+    fn catch_part_panic() {
+        let report = solve::<Panics>("7", &[1, 2], &Options::default());
+
+        assert_eq!(report.parts[0].answer, Ok(Answer::from(7)));
+        let failure = report.parts[1].answer.as_ref().unwrap_err();
+        assert!(failure.to_string().contains("no part 2"));
+        assert!(panic_location(failure).starts_with("src/runner.rs:"));
+    }
+
+    #[test]
+    fn catch_parse_panic() {
+        let report = solve::<Panics>("seven", &[1, 2], &Options::default());
+
+        assert!(report.parse.is_none());
+        for part in &report.parts {
+            let failure = part.answer.as_ref().unwrap_err();
+            assert_eq!(failure.kind(), "panicked");
+            assert!(failure.to_string().contains("ParseIntError"));
+        }
+    }
+
+    #[test]
+    fn compare_implementations() {
+        let reports = compare::<Panics>("7", &Options::default());
+        let summary: Vec<_> = reports
+            .iter()
+            .map(|report| (report.part, report.name, report.answer.as_ref().ok()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (1, "default", Some(&Answer::from(7))),
+                (1, "doubled", Some(&Answer::from(14))),
+                (2, "default", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_repeat_option() {
+        let args = ["--repeat", "10"].map(String::from).into_iter();
+        let options = Options::from_args(args).unwrap();
+
+        assert_eq!(options.repeat, 10);
+    }
+
+    #[test]
+    fn parse_format_option() {
+        let args = ["--format", "ndjson", "-r", "2"]
+            .map(String::from)
+            .into_iter();
+        let options = Options::from_args(args).unwrap();
+
+        assert_eq!(options.format, Format::Ndjson);
+        assert_eq!(options.repeat, 2);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_limit_options() {
+        let args = ["--timeout", "1.5", "-m", "64"]
+            .map(String::from)
+            .into_iter();
+        let options = Options::from_args(args).unwrap();
+
+        assert_eq!(options.timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(options.memory, Some(64 * 1024 * 1024));
+        assert!(options.isolated());
+    }
+
+    #[test]
+    #[cfg(not(unix))]
+    fn reject_unenforced_memory_limit() {
+        assert!(Options::from_args(["-m", "64"].map(String::from).into_iter()).is_err());
+    }
+
+    #[test]
+    fn reject_out_of_range_limits() {
+        for args in [["-t", "inf"], ["-t", "1e300"], ["-m", "99999999999999"]] {
+            assert!(Options::from_args(args.map(String::from).into_iter()).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_part_option() {
+        let options = Options::from_args(["-p", "2"].map(String::from).into_iter()).unwrap();
+
+        assert_eq!(options.parts(), &[2]);
+        assert_eq!(Options::default().parts(), &[1, 2]);
+        assert!(Options::from_args(["--part", "3"].map(String::from).into_iter()).is_err());
+    }
+
+    #[test]
+    fn force_or_measure_skips_cache() {
+        let force = Options::from_args(["--force"].map(String::from).into_iter()).unwrap();
+        let repeat = Options::from_args(["-r", "5"].map(String::from).into_iter()).unwrap();
+
+        assert!(!force.cached());
+        assert!(!repeat.cached());
+        assert_eq!(Options::default().cached(), !alloc::enabled());
+    }
+
+    #[test]
+    fn reject_zero_repeat() {
+        let args = ["-r", "0"].map(String::from).into_iter();
+
+        assert!(Options::from_args(args).is_err());
+    }
+}
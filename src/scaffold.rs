@@ -0,0 +1,340 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    examples::{self, DEFAULT_EXAMPLE, MANIFEST},
+    fetch, get_cached_input, input_path, DAYS,
+};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.in");
+
+const LIB_PATH: &str = "src/lib.rs";
+
+/// Creates the module and binary for a new day from the template, registers
+/// it and fetches the input and the first example from the site when a
+/// session cookie is available. The first day of a new year also creates
+/// the year's module and registers it in `src/lib.rs`.
+pub fn new_day(year: u32, day: u32) -> Result<(), String> {
+    if !(1..=DAYS).contains(&day) {
+        return Err(format!(
+            "day {day} is not in the calendar, which runs from 1 to {DAYS}"
+        ));
+    }
+
+    let module_path = format!("src/y{year}/day{day:02}.rs");
+    let bin_path = format!("src/bin/y{year}-day{day}.rs");
+    for path in [&module_path, &bin_path] {
+        if Path::new(path).exists() {
+            return Err(format!("{path} already exists"));
+        }
+    }
+
+    let days_path = format!("src/y{year}.rs");
+    let (days, lib) = if Path::new(&days_path).exists() {
+        let days = fs::read_to_string(&days_path)
+            .map_err(|e| format!("Could not read {days_path}: {e}"))?;
+
+        (register(&days, day)?, None)
+    } else {
+        let lib =
+            fs::read_to_string(LIB_PATH).map_err(|e| format!("Could not read {LIB_PATH}: {e}"))?;
+
+        (year_module(year, day), Some(register_year(&lib, year)?))
+    };
+
+    let examples_dir = examples::dir(year, day);
+    fs::create_dir_all(&examples_dir)
+        .map_err(|e| format!("Could not create {}: {e}", examples_dir.display()))?;
+
+    let example_path = examples::path(year, day, DEFAULT_EXAMPLE);
+    if !example_path.exists() {
+        if let Some(example) = fetch_example(year, day) {
+            write(&example_path.to_string_lossy(), &example).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let manifest_path = examples_dir.join(MANIFEST);
+    if !manifest_path.exists() {
+        write(&manifest_path.to_string_lossy(), "# name part answer\n")
+            .map_err(|e| e.to_string())?;
+    }
+
+    if get_cached_input(year, day).is_none() {
+        match fetch(year, &format!("day/{day}/input")) {
+            Ok(input) => write(&input_path(year, day).to_string_lossy(), &input)
+                .map_err(|e| e.to_string())?,
+            Err(e) => eprintln!("Could not fetch input: {e}"),
+        }
+    }
+
+    let bin = format!(
+        "use advent_of_code::{{y{year}::day{day:02}::Day{day}, runner}};\n\nfn main() {{\n    runner::main::<Day{day}>();\n}}\n"
+    );
+    let mut files = vec![
+        (module_path.as_str(), render(year, day)),
+        (bin_path.as_str(), bin),
+        (days_path.as_str(), days),
+    ];
+    if let Some(lib) = lib {
+        files.push((LIB_PATH, lib));
+    }
+
+    fs::create_dir_all(format!("src/y{year}"))
+        .map_err(|e| format!("Could not create src/y{year}: {e}"))?;
+    write_all(&files).map_err(|e| {
+        // Leave no empty directory for a year that was never registered.
+        let _ = fs::remove_dir(format!("src/y{year}"));
+        e.to_string()
+    })?;
+    println!(
+        "Add the example's expected answers to {} to make its tests pass",
+        manifest_path.display()
+    );
+
+    Ok(())
+}
+
+/// Writes the files in order, the registrations last. If one cannot be
+/// written, the files created before it are removed again, so that nothing is
+/// left that is not registered.
+fn write_all(files: &[(&str, String)]) -> io::Result<()> {
+    let mut created = Vec::new();
+    for &(path, ref content) in files {
+        let existed = Path::new(path).exists();
+        if let Err(e) = write(path, content) {
+            for path in created {
+                if fs::remove_file(path).is_ok() {
+                    println!("Removed {path}");
+                }
+            }
+            return Err(e);
+        }
+        if !existed {
+            created.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the file, naming it in the error if that fails.
+fn write(path: &str, content: &str) -> io::Result<()> {
+    fs::write(path, content)
+        .map_err(|e| io::Error::new(e.kind(), format!("Could not write {path}: {e}")))?;
+    println!("Wrote {path}");
+
+    Ok(())
+}
+
+fn fetch_example(year: u32, day: u32) -> Option<String> {
+    match fetch(year, &format!("day/{day}")) {
+        Ok(page) => {
+            let example = extract_example(&page);
+            if example.is_none() {
+                eprintln!("No example found in the puzzle description");
+            }
+
+            example
+        }
+        Err(e) => {
+            eprintln!("Could not fetch puzzle description: {e}");
+            None
+        }
+    }
+}
+
+/// Fills in the day template.
+pub fn render(year: u32, day: u32) -> String {
+    TEMPLATE
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY}}", &day.to_string())
+}
+
+/// The source of a new year's module, `src/y{year}.rs`, with its first day.
+pub fn year_module(year: u32, day: u32) -> String {
+    format!(
+        "//! Solutions to the puzzles of Advent of Code {year}, one module per day.
+
+pub mod day{day:02};
+
+use crate::Puzzle;
+
+pub const ALL: &[Puzzle] = &[
+    Puzzle::new::<day{day:02}::Day{day}>(),
+];
+"
+    )
+}
+
+/// Adds the year's module and puzzles to the source of `src/lib.rs`. The
+/// `YEARS` list may be on one line or one entry per line.
+pub fn register_year(source: &str, year: u32) -> Result<String, String> {
+    let module = format!("pub mod y{year};");
+    let entry = format!("({year}, y{year}::ALL)");
+    let not_found = || format!("Could not find where to register {year}");
+
+    if source.lines().any(|line| line == module) {
+        return Err(format!("{year} is already registered"));
+    }
+
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let last = lines
+        .iter()
+        .rposition(|line| line.starts_with("pub mod y"))
+        .ok_or_else(not_found)?;
+    lines.insert(last + 1, module);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const YEARS"))
+        .ok_or_else(not_found)?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.ends_with("];"))
+            .ok_or_else(not_found)?;
+    if start == end {
+        let line = &mut lines[end];
+        line.insert_str(line.len() - "];".len(), &format!(", {entry}"));
+    } else {
+        lines.insert(end, format!("    {entry},"));
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day's module and puzzle to the source of its year's module.
+pub fn register(source: &str, day: u32) -> Result<String, String> {
+    let module = format!("pub mod day{day:02};");
+    let puzzle = format!("    Puzzle::new::<day{day:02}::Day{day}>(),");
+
+    if source.lines().any(|line| line == module) {
+        return Err(format!("day {day} is already registered"));
+    }
+
+    let mut lines: Vec<&str> = source.lines().collect();
+    for (prefix, line) in [("pub mod day", &module), ("    Puzzle::new::<", &puzzle)] {
+        let last = lines
+            .iter()
+            .rposition(|line| line.starts_with(prefix))
+            .ok_or(format!("Could not find where to register day {day}"))?;
+
+        lines.insert(last + 1, line);
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The first code block of the puzzle description is almost always the
+/// example input.
+pub fn extract_example(page: &str) -> Option<String> {
+    let start = page.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + page[start..].find("</code></pre>")?;
+
+    let example = page[start..end]
+        .replace("<em>", "")
+        .replace("</em>", "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    Some(example)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{extract_example, new_day, register, register_year, render, year_module};
+
+    #[test]
+    fn reject_days_off_the_calendar() {
+        assert!(new_day(2024, 0)
+            .unwrap_err()
+            .contains("not in the calendar"));
+        assert!(new_day(2024, 26)
+            .unwrap_err()
+            .contains("not in the calendar"));
+    }
+
+    #[test]
+    fn register_after_last_day() {
+        let source = "pub mod day09;
+
+use crate::Puzzle;
+
+pub const ALL: &[Puzzle] = &[
+    Puzzle::new::<day09::Day9>(),
+];
+";
+        let expected = "pub mod day09;
+pub mod day10;
+
+use crate::Puzzle;
+
+pub const ALL: &[Puzzle] = &[
+    Puzzle::new::<day09::Day9>(),
+    Puzzle::new::<day10::Day10>(),
+];
+";
+
+        assert_eq!(register(source, 10).unwrap(), expected);
+        assert!(register(expected, 10).is_err());
+    }
+
+    #[test]
+    fn register_new_year() {
+        let source = "pub mod y2024;
+
+pub const YEARS: &[(u32, &[Puzzle])] = &[
+    (2024, y2024::ALL),
+];
+";
+        let expected = "pub mod y2024;
+pub mod y2025;
+
+pub const YEARS: &[(u32, &[Puzzle])] = &[
+    (2024, y2024::ALL),
+    (2025, y2025::ALL),
+];
+";
+
+        assert_eq!(register_year(source, 2025).unwrap(), expected);
+        assert!(register_year(expected, 2025).is_err());
+        assert_eq!(
+            register_year(
+                "pub mod y2024;\n\npub const YEARS: &[(u32, &[Puzzle])] = &[(2024, y2024::ALL)];",
+                2025
+            )
+            .unwrap(),
+            "pub mod y2024;\npub mod y2025;\n\npub const YEARS: &[(u32, &[Puzzle])] = &[(2024, y2024::ALL), (2025, y2025::ALL)];\n"
+        );
+        assert_eq!(
+            register(&year_module(2025, 1), 2).unwrap(),
+            year_module(2025, 1)
+                .replace("pub mod day01;\n", "pub mod day01;\npub mod day02;\n")
+                .replace(
+                    "Day1>(),\n",
+                    "Day1>(),\n    Puzzle::new::<day02::Day2>(),\n"
+                )
+        );
+    }
+
+    #[test]
+    fn extract_first_code_block() {
+        let page = "<p>For example:</p>\n<pre><code>3   4\n<em>4</em>   3\n</code></pre>\n<pre><code>other</code></pre>";
+
+        assert_eq!(extract_example(page).unwrap(), "3   4\n4   3\n");
+        assert_eq!(extract_example("<p>no example</p>"), None);
+    }
+
+    #[test]
+    fn render_template() {
+        let rendered = render(2024, 7);
+
+        assert!(rendered.contains("pub struct Day7;"));
+        assert!(rendered.contains("const YEAR: u32 = 2024;"));
+        assert!(rendered.contains("const DAY: u32 = 7;"));
+        assert!(rendered.contains("example_tests!(super::Day7);"));
+        assert!(!rendered.contains("{{"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    answer::Answer,
+    cache, isolate,
+    report::Visualisation,
+    runner::{self, ImplementationReport, Options, Report},
+};
+
+/// A single day's puzzle, split into a parse step and the two parts so the
+/// runner can time each of them separately.
+pub trait Solution {
+    const YEAR: u32;
+
+    const DAY: u32;
+
+    type Parsed;
+
+    fn parse(input: &str) -> Self::Parsed;
+
+    fn part1(parsed: &Self::Parsed) -> impl Into<Answer>;
+
+    fn part2(parsed: &Self::Parsed) -> impl Into<Answer>;
+
+    /// Other implementations of the parts, e.g. a naive version kept next to
+    /// an optimised one. The compare mode checks that they agree with `part1`
+    /// and `part2` and times them side by side.
+    fn alternatives() -> Vec<Implementation<Self::Parsed>> {
+        Vec::new()
+    }
+
+    /// A drawing of the solution, e.g. the path a guard walks, shown in the
+    /// HTML report.
+    fn visualise(_parsed: &Self::Parsed) -> Option<Visualisation> {
+        None
+    }
+}
+
+/// A named alternative implementation of one part of a [`Solution`].
+pub struct Implementation<P> {
+    pub name: &'static str,
+    pub part: u32,
+    pub solve: fn(&P) -> Answer,
+}
+
+/// A registered day. Erases the [`Solution`] type so every day can be kept in
+/// one list and run by the `aoc` binary.
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    solve: fn(&str, &[u32], &Options) -> Report,
+    compare: fn(&str, &Options) -> Vec<ImplementationReport>,
+    visualise: fn(&str) -> Option<Visualisation>,
+}
+
+impl Puzzle {
+    pub const fn new<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            solve: runner::solve::<S>,
+            compare: runner::compare::<S>,
+            visualise: visualise::<S>,
+        }
+    }
+
+    /// Loads the day's input and solves the parts the options ask for,
+    /// reusing cached answers unless the options rule it out.
+    pub fn run(&self, options: &Options) -> Report {
+        let (input, load) = runner::load_input(self.year, self.day, options.example);
+
+        let mut report = cache::solve(self, &input, options);
+        report.load = load;
+
+        report
+    }
+
+    /// Solves the parts the options ask for, each in its own child process
+    /// when they set a timeout or memory limit.
+    pub fn solve(&self, input: &str, options: &Options) -> Report {
+        if options.isolated() {
+            isolate::solve(self, input, options)
+        } else {
+            self.solve_parts(input, options.parts(), options)
+        }
+    }
+
+    /// Solves the given parts in this process.
+    pub fn solve_parts(&self, input: &str, parts: &[u32], options: &Options) -> Report {
+        (self.solve)(input, parts, options)
+    }
+
+    /// Loads the day's input and runs every implementation of the parts the
+    /// options ask for, in this process.
+    pub fn compare(&self, options: &Options) -> Vec<ImplementationReport> {
+        let (input, _) = runner::load_input(self.year, self.day, options.example);
+
+        (self.compare)(&input, options)
+    }
+
+    /// Draws the solution on the given input, if the day has a drawing.
+    pub fn visualise(&self, input: &str) -> Option<Visualisation> {
+        (self.visualise)(input)
+    }
+}
+
+fn visualise<S: Solution>(input: &str) -> Option<Visualisation> {
+    S::visualise(&S::parse(input))
+}
@@ -0,0 +1,162 @@
+use std::fmt::{self, Display};
+
+use crate::{post, Answer};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    /// Wrong, with the site's hint such as `your answer is too high`.
+    Incorrect {
+        hint: Option<String>,
+    },
+    /// Submitted too soon after a wrong answer, with the time left to wait.
+    TooSoon {
+        wait: Option<String>,
+    },
+    /// The part was already solved, or part 1 is still unsolved.
+    WrongLevel,
+    /// The response did not match any known message.
+    Unknown(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect { hint: None } => write!(f, "incorrect"),
+            Outcome::Incorrect { hint: Some(hint) } => write!(f, "incorrect, {hint}"),
+            Outcome::TooSoon { wait: None } => write!(f, "submitted too recently"),
+            Outcome::TooSoon { wait: Some(wait) } => {
+                write!(f, "submitted too recently, {wait} left to wait")
+            }
+            Outcome::WrongLevel => write!(f, "not the current level, is it already solved?"),
+            Outcome::Unknown(message) => write!(f, "unrecognised response: {message}"),
+        }
+    }
+}
+
+/// Submits an answer for a part of the puzzle for `day` of `year`.
+pub fn submit(year: u32, day: u32, part: u32, answer: &Answer) -> Result<Outcome, String> {
+    let answer = answer.letters();
+    if answer.is_multiline() {
+        return Err(format!(
+            "The answer is multi-line text that could not be read as letters:\n{answer}"
+        ));
+    }
+
+    let level = part.to_string();
+    let answer = answer.to_string();
+    let page = post(
+        year,
+        &format!("day/{day}/answer"),
+        &[("level", &level), ("answer", &answer)],
+    )?;
+
+    Ok(parse_outcome(&page))
+}
+
+/// Reads the verdict from the message in the `<article>` of the response.
+pub fn parse_outcome(page: &str) -> Outcome {
+    let message = page
+        .find("<article>")
+        .and_then(|start| {
+            let end = start + page[start..].find("</article>")?;
+            Some(&page[start + "<article>".len()..end])
+        })
+        .map(strip_tags)
+        .unwrap_or_default();
+
+    if message.contains("That's the right answer") {
+        Outcome::Correct
+    } else if message.contains("That's not the right answer") {
+        let hint = ["too high", "too low"]
+            .into_iter()
+            .find(|hint| message.contains(hint))
+            .map(|hint| format!("your answer is {hint}"));
+
+        Outcome::Incorrect { hint }
+    } else if message.contains("You gave an answer too recently") {
+        let wait = message
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+
+        Outcome::TooSoon { wait }
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(message)
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_outcome, Outcome};
+
+    fn page(message: &str) -> String {
+        format!("<html><main>\n<article><p>{message}</p></article>\n</main></html>")
+    }
+
+    #[test]
+    fn correct_answer() {
+        let page = page("That's the right answer!  You are <span>one gold star</span> closer.");
+
+        assert_eq!(parse_outcome(&page), Outcome::Correct);
+    }
+
+    #[test]
+    fn incorrect_answer_with_hint() {
+        let page = page("That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.");
+
+        assert_eq!(
+            parse_outcome(&page),
+            Outcome::Incorrect {
+                hint: Some("your answer is too low".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn answer_too_soon() {
+        let page = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 41s left to wait. <a href=\"/2024/day/6\">[Return to Day 6]</a>");
+
+        assert_eq!(
+            parse_outcome(&page),
+            Outcome::TooSoon {
+                wait: Some("41s".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn already_solved() {
+        let page =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+
+        assert_eq!(parse_outcome(&page), Outcome::WrongLevel);
+    }
+
+    #[test]
+    fn unknown_response() {
+        assert_eq!(
+            parse_outcome(&page("Something <em>else</em>")),
+            Outcome::Unknown("Something else".to_string())
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{examples, input_path};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The outcome of one rebuild and run of a day.
+#[derive(Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub answers: Vec<String>,
+    /// Test name to whether it passed.
+    pub tests: BTreeMap<String, bool>,
+    pub errors: Vec<String>,
+}
+
+/// Re-runs a day every time its source, the shared library or its input
+/// changes, printing the answers and test results of each run and how they
+/// differ from the previous one. Runs until interrupted.
+pub fn watch(year: u32, day: u32, example: bool) -> Result<(), String> {
+    let files = watched_files(year, day, example)?;
+
+    eprintln!("Watching {} files for day {day} of {year}", files.len());
+
+    let mut last_modified = Vec::new();
+    let mut last = None;
+    loop {
+        let modified: Vec<_> = files.iter().map(modified).collect();
+        if modified != last_modified {
+            let changed: Vec<_> = files
+                .iter()
+                .zip(&modified)
+                .enumerate()
+                .filter(|(i, (_, time))| last_modified.get(*i) != Some(time))
+                .map(|(_, (file, _))| file.display().to_string())
+                .collect();
+            last_modified = modified;
+
+            if last.is_some() {
+                println!("\nChanged: {}", changed.join(", "));
+            }
+
+            let snapshot = snapshot(year, day, example);
+            print_diff(&snapshot, last.as_ref());
+            last = Some(snapshot);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn watched_files(year: u32, day: u32, example: bool) -> Result<Vec<PathBuf>, String> {
+    let mut files = vec![
+        PathBuf::from(format!("src/y{year}/day{day:02}.rs")),
+        PathBuf::from(format!("src/bin/y{year}-day{day}.rs")),
+    ];
+
+    // The shared library is everything at the top level of `src`.
+    let entries = fs::read_dir("src").map_err(|e| format!("Could not read src: {e}"))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+
+    files.push(if example {
+        examples::path(year, day, examples::DEFAULT_EXAMPLE)
+    } else {
+        input_path(year, day)
+    });
+    // The tests check every example against the manifest.
+    files.push(examples::dir(year, day).join(examples::MANIFEST));
+
+    Ok(files)
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn snapshot(year: u32, day: u32, example: bool) -> Snapshot {
+    let mut snapshot = Snapshot::default();
+
+    let bin = format!("y{year}-day{day}");
+    let mut run_args = vec!["run", "--quiet", "--bin", &bin, "--", "--format", "ndjson"];
+    if example {
+        run_args.push("--example");
+    }
+
+    match cargo(&run_args) {
+        Ok(output) if output.status.success() => {
+            snapshot.answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+        }
+        Ok(output) => snapshot.errors.push(failure("run", &output)),
+        Err(e) => snapshot.errors.push(e),
+    }
+
+    let filter = format!("y{year}::day{day:02}::");
+    match cargo(&["test", "--lib", &filter]) {
+        Ok(output) => {
+            snapshot.tests = parse_tests(&String::from_utf8_lossy(&output.stdout));
+            if !output.status.success() && snapshot.tests.is_empty() {
+                snapshot.errors.push(failure("test", &output));
+            }
+        }
+        Err(e) => snapshot.errors.push(e),
+    }
+
+    snapshot
+}
+
+fn cargo(args: &[&str]) -> Result<Output, String> {
+    let cargo = env::var("CARGO").unwrap_or("cargo".to_string());
+
+    Command::new(cargo)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Could not run cargo: {e}"))
+}
+
+fn failure(what: &str, output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let tail: Vec<_> = stderr.lines().rev().take(20).collect();
+    let tail: Vec<_> = tail.into_iter().rev().collect();
+
+    format!("{what} failed:\n{}", tail.join("\n"))
+}
+
+/// Picks the answers out of the day binary's NDJSON output.
+fn parse_answers(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .map(|record| match &record["answer"] {
+            serde_json::Value::String(answer) => answer.clone(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+/// Picks the results out of the libtest output, e.g.
+/// `test y2024::day04::test::count_xmas_single ... ok`.
+fn parse_tests(stdout: &str) -> BTreeMap<String, bool> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, result) = line.strip_prefix("test ")?.split_once(" ... ")?;
+            match result {
+                "ok" => Some((name.to_string(), true)),
+                "FAILED" => Some((name.to_string(), false)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn print_diff(current: &Snapshot, last: Option<&Snapshot>) {
+    for error in &current.errors {
+        println!("{error}");
+    }
+
+    for (i, answer) in current.answers.iter().enumerate() {
+        let previous = last.and_then(|last| last.answers.get(i));
+        match previous {
+            Some(previous) if previous != answer => {
+                println!("part {}  {answer}  (was {previous})", i + 1)
+            }
+            _ => println!("part {}  {answer}", i + 1),
+        }
+    }
+
+    let passed = current.tests.values().filter(|passed| **passed).count();
+    let failed = current.tests.len() - passed;
+    println!("tests   {passed} passed, {failed} failed");
+
+    for (name, passed) in &current.tests {
+        let previous = last.and_then(|last| last.tests.get(name));
+        let result = if *passed { "ok" } else { "FAILED" };
+        match previous {
+            None if last.is_some() => println!("  {name}  {result} (new)"),
+            Some(previous) if previous != passed => {
+                let was = if *previous { "ok" } else { "FAILED" };
+                println!("  {name}  {was} -> {result}");
+            }
+            _ if !passed => println!("  {name}  {result}"),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_answers, parse_tests};
+
+    #[test]
+    fn answers_from_ndjson() {
+        let stdout = "Reading input from file\n{\"answer\":\"18\",\"part\":1}\n{\"answer\":\"9\",\"part\":2}\n";
+
+        assert_eq!(parse_answers(stdout), ["18", "9"]);
+    }
+
+    #[test]
+    fn tests_from_libtest_output() {
+        let stdout = "running 2 tests
+test y2024::day04::test::count_xmas_single ... ok
+test y2024::day04::test::count_mas_x_single ... FAILED
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out";
+        let tests = parse_tests(stdout);
+
+        assert_eq!(tests.len(), 2);
+        assert!(tests["y2024::day04::test::count_xmas_single"]);
+        assert!(!tests["y2024::day04::test::count_mas_x_single"]);
+    }
+}
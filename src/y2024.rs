@@ -0,0 +1,19 @@
+//! Solutions to the puzzles of Advent of Code 2024, one module per day.
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+
+use crate::Puzzle;
+
+pub const ALL: &[Puzzle] = &[
+    Puzzle::new::<day01::Day1>(),
+    Puzzle::new::<day02::Day2>(),
+    Puzzle::new::<day03::Day3>(),
+    Puzzle::new::<day04::Day4>(),
+    Puzzle::new::<day05::Day5>(),
+    Puzzle::new::<day06::Day6>(),
+];
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::{Answer, Implementation, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2024;
+
+    const DAY: u32 = 1;
+
+    type Parsed = (Vec<u32>, Vec<u32>);
+
+    fn parse(input: &str) -> Self::Parsed {
+        let mut lhs = Vec::new();
+        let mut rhs = Vec::new();
+
+        for line in input.lines() {
+            let mut nums = line.split("   ");
+            let num1 = nums.next().unwrap().parse::<u32>().unwrap();
+            let num2 = nums.next().unwrap().parse::<u32>().unwrap();
+
+            lhs.push(num1);
+            rhs.push(num2);
+        }
+
+        lhs.sort();
+        rhs.sort();
+
+        (lhs, rhs)
+    }
+
+    fn part1((lhs, rhs): &Self::Parsed) -> impl Into<Answer> {
+        let mut sum = 0;
+        for (num1, num2) in lhs.iter().zip(rhs) {
+            let diff = num1.abs_diff(*num2);
+
+            sum += diff;
+        }
+
+        sum
+    }
+
+    fn part2((lhs, rhs): &Self::Parsed) -> impl Into<Answer> {
+        let mut nums = HashMap::new();
+        let mut last_num = None;
+        let mut num_n = 0;
+        for num in rhs.iter() {
+            let Some(last) = last_num else {
+                last_num = Some(*num);
+                num_n += 1;
+                continue;
+            };
+
+            if *num == last {
+                num_n += 1;
+            } else {
+                nums.insert(last, num_n);
+                num_n = 1;
+                last_num = Some(*num);
+            }
+        }
+
+        nums.insert(last_num.unwrap(), num_n);
+
+        let mut sum = 0;
+        for num in lhs.iter() {
+            let count = if let Some(num) = nums.get(num) {
+                *num
+            } else {
+                0
+            };
+
+            sum += count * *num;
+        }
+
+        sum
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Parsed>> {
+        vec![Implementation {
+            name: "hash_count",
+            part: 2,
+            solve: |parsed| similarity_hash_count(parsed).into(),
+        }]
+    }
+}
+
+/// Counts the right list into a map without relying on it being sorted.
+fn similarity_hash_count((lhs, rhs): &(Vec<u32>, Vec<u32>)) -> u32 {
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for num in rhs {
+        *counts.entry(*num).or_default() += 1;
+    }
+
+    lhs.iter()
+        .map(|num| num * counts.get(num).copied().unwrap_or_default())
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    crate::example_tests!(super::Day1);
+}
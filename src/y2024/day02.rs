@@ -0,0 +1,101 @@
+use crate::{Answer, Solution};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2024;
+
+    const DAY: u32 = 2;
+
+    type Parsed = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Self::Parsed {
+        input
+            .lines()
+            .map(|line| line.split(" ").map(|n| n.parse().unwrap()).collect())
+            .collect()
+    }
+
+    fn part1(reports: &Self::Parsed) -> impl Into<Answer> {
+        reports.iter().filter(|levels| is_safe(levels)).count()
+    }
+
+    fn part2(reports: &Self::Parsed) -> impl Into<Answer> {
+        let mut safe_count = 0;
+        for levels in reports {
+            if is_safe(levels) {
+                safe_count += 1;
+                continue;
+            } else {
+                for skip_index in 0..levels.len() {
+                    let mut levels = levels.clone();
+                    levels.remove(skip_index);
+
+                    if is_safe(&levels) {
+                        safe_count += 1;
+                        break;
+                    }
+                }
+            }
+        }
+
+        safe_count
+    }
+}
+
+/// Whether the levels of a report only increase or only decrease, by at
+/// most three at a time.
+pub fn is_safe(levels: &[u32]) -> bool {
+    let mut asc = None;
+
+    for i in 0..levels.len() - 1 {
+        let l1 = levels[i];
+        let l2 = levels[i + 1];
+
+        asc = match l1 {
+            l if l < l2 => Some(true),
+            l if l > l2 => Some(false),
+            _ => return false,
+        };
+    }
+
+    let asc = asc.unwrap();
+
+    for i in 0..levels.len() - 1 {
+        let l1 = levels[i];
+        let l2 = levels[i + 1];
+
+        if is_problem(l1, l2, asc) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn is_problem(l1: u32, l2: u32, asc: bool) -> bool {
+    if l1 == l2 {
+        return false;
+    }
+
+    if asc {
+        if l1 > l2 {
+            return true;
+        }
+    } else if l1 < l2 {
+        return true;
+    }
+
+    let diff = l1.abs_diff(l2);
+
+    if !(1..=3).contains(&diff) {
+        return true;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    crate::example_tests!(super::Day2);
+}
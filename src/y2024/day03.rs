@@ -1,9 +1,9 @@
-use std::{io::Read, iter, str::Chars};
+use std::{iter, str::Chars};
 
-use advent_of_code::get_input;
+use crate::{Answer, Solution};
 
 #[derive(Debug, PartialEq)]
-enum Token {
+pub enum Token {
     Mul,
     Do,
     Dont,
@@ -130,16 +130,31 @@ impl Cursor<'_> {
     }
 }
 
-pub fn main() {
-    let mut input = String::new();
-    get_input(3).read_to_string(&mut input).unwrap();
+pub struct Day3;
 
-    let sum = eval(&input, true);
+impl Solution for Day3 {
+    const YEAR: u32 = 2024;
 
-    println!("{sum}");
+    const DAY: u32 = 3;
+
+    type Parsed = String;
+
+    fn parse(input: &str) -> Self::Parsed {
+        input.to_string()
+    }
+
+    fn part1(memory: &Self::Parsed) -> impl Into<Answer> {
+        eval(memory, false)
+    }
+
+    fn part2(memory: &Self::Parsed) -> impl Into<Answer> {
+        eval(memory, true)
+    }
 }
 
-fn eval(input: &str, use_conditionals: bool) -> u32 {
+/// Sums the products of the valid `mul(a,b)` instructions, skipping those
+/// after a `don't()` when `use_conditionals` is set.
+pub fn eval(input: &str, use_conditionals: bool) -> u32 {
     let mut tokens = tokens(input);
     let mut sum = 0;
 
@@ -204,7 +219,7 @@ fn eval(input: &str, use_conditionals: bool) -> u32 {
     sum
 }
 
-fn tokens(input: &str) -> impl Iterator<Item = Token> + '_ {
+pub fn tokens(input: &str) -> impl Iterator<Item = Token> + '_ {
     let mut cursor = Cursor::new(input);
     iter::from_fn(move || {
         if cursor.is_eof() {
@@ -218,7 +233,9 @@ fn tokens(input: &str) -> impl Iterator<Item = Token> + '_ {
 
 #[cfg(test)]
 mod test {
-    use crate::{eval, tokens, Token};
+    use super::{eval, tokens, Token};
+
+    crate::example_tests!(super::Day3);
 
     #[test]
     fn tokenize_basic_input() {
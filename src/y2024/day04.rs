@@ -0,0 +1,187 @@
+use std::collections::BTreeSet;
+
+use crate::{report::Visualisation, Answer, Grid, Pos, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2024;
+
+    const DAY: u32 = 4;
+
+    type Parsed = WordSearch;
+
+    fn parse(input: &str) -> Self::Parsed {
+        WordSearch::new(input.as_bytes())
+    }
+
+    fn part1(word_search: &Self::Parsed) -> impl Into<Answer> {
+        word_search.count_xmas()
+    }
+
+    fn part2(word_search: &Self::Parsed) -> impl Into<Answer> {
+        word_search.count_mas_x()
+    }
+
+    fn visualise(word_search: &Self::Parsed) -> Option<Visualisation> {
+        Some(Visualisation {
+            caption: "letters of every XMAS".to_string(),
+            rows: word_search.letters.lines(|&letter| letter as char),
+            marked: word_search.xmas_cells(),
+        })
+    }
+}
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (-1, 1),
+    (1, -1),
+    (-1, -1),
+];
+
+pub struct WordSearch {
+    letters: Grid<u8>,
+}
+
+impl WordSearch {
+    pub fn new(input: &[u8]) -> Self {
+        let letters = Grid::from_bytes(input, |letter| letter).expect("Invalid word search");
+
+        Self { letters }
+    }
+
+    /// Occurrences of `XMAS` in any of the eight directions.
+    pub fn count_xmas(&self) -> usize {
+        self.letters
+            .cells()
+            .filter(|(_, &letter)| letter == b'X')
+            .map(|(pos, _)| {
+                DIRECTIONS
+                    .iter()
+                    .filter(|&&direction| self.check_xmas(pos, direction))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The cells of the letters that are part of any `XMAS`, by row and
+    /// column.
+    pub fn xmas_cells(&self) -> BTreeSet<Pos> {
+        let mut cells = BTreeSet::new();
+
+        for (pos, &letter) in self.letters.cells() {
+            if letter != b'X' {
+                continue;
+            }
+
+            for (rd, cd) in DIRECTIONS {
+                if self.check_xmas(pos, (rd, cd)) {
+                    cells.extend((0..4).filter_map(|i| self.letters.offset(pos, (rd * i, cd * i))));
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// Occurrences of two `MAS` crossing in an X.
+    pub fn count_mas_x(&self) -> usize {
+        self.letters
+            .cells()
+            .filter(|&(pos, &letter)| letter == b'A' && self.check_mas_x(pos))
+            .count()
+    }
+
+    fn check_xmas(&self, pos: Pos, (rd, cd): (isize, isize)) -> bool {
+        b"XMAS".iter().enumerate().all(|(i, letter)| {
+            let i = i as isize;
+
+            self.letters.get_offset(pos, (rd * i, cd * i)) == Some(letter)
+        })
+    }
+
+    fn check_mas_x(&self, pos: Pos) -> bool {
+        let letter = |delta| self.letters.get_offset(pos, delta).copied();
+        let is_mas = |ends| matches!(ends, (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M')));
+
+        is_mas((letter((-1, -1)), letter((1, 1)))) && is_mas((letter((-1, 1)), letter((1, -1))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WordSearch;
+
+    crate::example_tests!(super::Day4);
+
+    #[test]
+    fn count_xmas_test_data() {
+        let input = r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX"#;
+
+        let expected = 18;
+        let actual = WordSearch::new(input.as_bytes()).count_xmas();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn count_xmas_single() {
+        let input = [
+            "XMAS\n",
+            "SAMX\n",
+            r#"X
+M
+A
+S"#,
+            r#"S
+A
+M
+X"#,
+        ];
+
+        for input in input {
+            let actual = WordSearch::new(input.as_bytes()).count_xmas();
+            assert_eq!(actual, 1, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn count_mas_x_single() {
+        let input = r#"SFM
+FAF
+SFM"#;
+        let actual = WordSearch::new(input.as_bytes()).count_mas_x();
+
+        assert_eq!(actual, 1);
+    }
+
+    #[test]
+    fn count_mas_x_test_data() {
+        let input = r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX"#;
+        let actual = WordSearch::new(input.as_bytes()).count_mas_x();
+
+        assert_eq!(actual, 9);
+    }
+}
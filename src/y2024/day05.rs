@@ -1,44 +1,62 @@
 use std::collections::HashMap;
-use std::io::BufRead;
 
-use advent_of_code::get_input;
+use crate::{debug, trace, Answer, Solution};
 
-fn main() {
-    let input = get_input(5);
+pub struct Day5;
 
-    let lines = input.lines().filter_map(|line| {
-        if let Ok(line) = line {
-            Some(line)
-        } else {
-            None
-        }
-    });
+impl Solution for Day5 {
+    const YEAR: u32 = 2024;
+
+    const DAY: u32 = 5;
+
+    type Parsed = Manual;
+
+    fn parse(input: &str) -> Self::Parsed {
+        parse(input.lines())
+    }
 
-    //let correct = count(lines);
-    let correct = sort_and_sum(lines);
+    fn part1(manual: &Self::Parsed) -> impl Into<Answer> {
+        count(manual)
+    }
 
-    println!("{correct}");
+    fn part2(manual: &Self::Parsed) -> impl Into<Answer> {
+        sort_and_sum(manual)
+    }
 }
 
-fn count(mut lines: impl Iterator<Item = impl AsRef<str>>) -> usize {
+pub struct Manual {
+    /// The pages that must come before each page.
+    pub rules: HashMap<usize, Vec<usize>>,
+    pub updates: Vec<Vec<usize>>,
+}
+
+pub fn parse(mut lines: impl Iterator<Item = impl AsRef<str>>) -> Manual {
     let rule_lines = lines
         .by_ref()
         .take_while(|line| !line.as_ref().trim().is_empty());
     let rules = parse_rules(rule_lines);
 
-    sum_valid_middles(lines, &rules)
+    let updates = lines
+        .map(|line| {
+            line.as_ref()
+                .split(',')
+                .map(|n| n.parse::<usize>().unwrap())
+                .collect()
+        })
+        .collect();
+
+    Manual { rules, updates }
 }
 
-fn sort_and_sum(mut lines: impl Iterator<Item = impl AsRef<str>>) -> usize {
-    let rule_lines = lines
-        .by_ref()
-        .take_while(|line| !line.as_ref().trim().is_empty());
-    let rules = parse_rules(rule_lines);
+pub fn count(manual: &Manual) -> usize {
+    sum_valid_middles(&manual.updates, &manual.rules)
+}
 
-    sort_and_sum_invalids(lines, &rules)
+pub fn sort_and_sum(manual: &Manual) -> usize {
+    sort_and_sum_invalids(&manual.updates, &manual.rules)
 }
 
-fn parse_rules(lines: impl Iterator<Item = impl AsRef<str>>) -> HashMap<usize, Vec<usize>> {
+pub fn parse_rules(lines: impl Iterator<Item = impl AsRef<str>>) -> HashMap<usize, Vec<usize>> {
     let mut rules = HashMap::new();
     for line in lines {
         let line = line.as_ref();
@@ -54,19 +72,10 @@ fn parse_rules(lines: impl Iterator<Item = impl AsRef<str>>) -> HashMap<usize, V
     rules
 }
 
-fn sum_valid_middles(
-    update_lines: impl Iterator<Item = impl AsRef<str>>,
-    rules: &HashMap<usize, Vec<usize>>,
-) -> usize {
+pub fn sum_valid_middles(updates: &[Vec<usize>], rules: &HashMap<usize, Vec<usize>>) -> usize {
     let mut sum = 0;
-    for line in update_lines {
-        let line = line.as_ref();
-        let nums: Vec<usize> = line
-            .split(',')
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect();
-
-        if is_valid(&nums, rules) {
+    for nums in updates {
+        if is_valid(nums, rules) {
             let middle_idx = nums.len() / 2;
             sum += nums[middle_idx];
         }
@@ -75,20 +84,13 @@ fn sum_valid_middles(
     sum
 }
 
-fn sort_and_sum_invalids(
-    update_lines: impl Iterator<Item = impl AsRef<str>>,
-    rules: &HashMap<usize, Vec<usize>>,
-) -> usize {
+pub fn sort_and_sum_invalids(updates: &[Vec<usize>], rules: &HashMap<usize, Vec<usize>>) -> usize {
     let mut sum = 0;
 
-    for (line_no, line) in update_lines.enumerate() {
-        let line = line.as_ref();
-        let mut nums: Vec<usize> = line
-            .split(',')
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect();
+    for (line_no, nums) in updates.iter().enumerate() {
+        let mut nums = nums.clone();
 
-        println!("Line: {line_no}");
+        trace!("Line: {line_no}");
         let mut i = 0;
         let len = nums.len();
         let mut was_invalid = false;
@@ -106,11 +108,11 @@ fn sort_and_sum_invalids(
                 .rev()
                 .find(|(_, other_num)| iter_has(before.iter(), other_num))
             {
-                println!("Error: {num} is before {other_num}");
+                debug!("Error: {num} is before {other_num}");
 
                 let r_num = nums.remove(i);
                 assert_eq!(num, r_num);
-                println!("Moving {num} (idx {i}) to idx {j}");
+                debug!("Moving {num} (idx {i}) to idx {j}");
                 nums.insert(j, num);
                 was_invalid = true;
             } else {
@@ -129,7 +131,7 @@ fn sort_and_sum_invalids(
     sum
 }
 
-fn is_valid(nums: &[usize], rules: &HashMap<usize, Vec<usize>>) -> bool {
+pub fn is_valid(nums: &[usize], rules: &HashMap<usize, Vec<usize>>) -> bool {
     for (i, num) in nums.iter().enumerate() {
         let Some(before) = rules.get(num) else {
             continue;
@@ -156,7 +158,9 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::{count, sort_and_sum};
+    use super::{count, parse, sort_and_sum};
+
+    crate::example_tests!(super::Day5);
 
     #[test]
     fn count_valid_updates_test_input() {
@@ -189,7 +193,7 @@ mod test {
 61,13,29
 97,13,75,29,47"#;
 
-        let valid = count(input.lines());
+        let valid = count(&parse(input.lines()));
 
         assert_eq!(valid, 143);
     }
@@ -225,7 +229,7 @@ mod test {
 61,13,29
 97,13,75,29,47"#;
 
-        let sum = sort_and_sum(input.lines());
+        let sum = sort_and_sum(&parse(input.lines()));
 
         assert_eq!(sum, 123);
     }
@@ -238,7 +242,7 @@ mod test {
 53|13
 
 97,13,75,53,47"#;
-        let sum = sort_and_sum(input.lines());
+        let sum = sort_and_sum(&parse(input.lines()));
 
         assert_eq!(sum, 47);
     }
@@ -1,30 +1,135 @@
 use core::panic;
 use std::collections::HashSet;
-use std::io::BufRead;
 
-use advent_of_code::get_input;
+use crate::{
+    debug, error, report::Visualisation, trace, Answer, Grid, Implementation, Pos, Solution,
+};
 
-fn main() {
-    let input = get_input(6);
+pub struct Day6;
 
-    let map = Map::from_lines(input.lines().map_while(Result::ok));
+impl Solution for Day6 {
+    const YEAR: u32 = 2024;
 
-    path_count(map.clone());
-    loop_count(map);
+    const DAY: u32 = 6;
+
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Self::Parsed {
+        Map::from_lines(input.lines())
+    }
+
+    fn part1(map: &Self::Parsed) -> impl Into<Answer> {
+        path_count(map.clone())
+    }
+
+    fn part2(map: &Self::Parsed) -> impl Into<Answer> {
+        loop_count(map.clone())
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Parsed>> {
+        vec![Implementation {
+            name: "grid_walk",
+            part: 2,
+            solve: |map| loop_count_grid_walk(map).into(),
+        }]
+    }
+
+    fn visualise(map: &Self::Parsed) -> Option<Visualisation> {
+        let mut map = map.clone();
+        map.sim_path().ok()?;
+
+        Some(map.visualisation())
+    }
+}
+
+/// Cells the guard visits before leaving the map.
+pub fn path_count(mut map: Map) -> usize {
+    map.sim_path().expect("loop dectected").visited()
+}
+
+/// Cells where a new obstruction would trap the guard in a loop.
+pub fn loop_count(mut map: Map) -> usize {
+    map.find_loops()
 }
 
-fn path_count(mut map: Map) {
-    let count = map.sim_path().expect("loop dectected").visited();
-    println!("{count}");
+/// Walks the guard over a plain obstacle grid instead of re-simulating the
+/// tile map, trying an obstruction on each cell of the original path.
+pub fn loop_count_grid_walk(map: &Map) -> usize {
+    let (r, c) = map.guard_pos().expect("No guard on map");
+    let direction = *map.tiles[(r, c)]
+        .guard_direction()
+        .expect("Guard not in expected position");
+    let width = map.tiles.width();
+    let start = r * width + c;
+
+    let obstacles: Vec<bool> = map
+        .tiles
+        .cells()
+        .map(|(_, tile)| matches!(tile, Tile::Obstacle))
+        .collect();
+
+    let mut seen = vec![0; obstacles.len()];
+    if walk(&obstacles, width, start, direction, None, &mut seen) {
+        panic!("Found loop in base map");
+    }
+    let path: Vec<usize> = (0..seen.len())
+        .filter(|&pos| seen[pos] != 0 && pos != start)
+        .collect();
+
+    path.into_iter()
+        .filter(|&obstruction| {
+            seen.fill(0);
+            walk(
+                &obstacles,
+                width,
+                start,
+                direction,
+                Some(obstruction),
+                &mut seen,
+            )
+        })
+        .count()
 }
 
-fn loop_count(mut map: Map) {
-    let loops = map.find_loops();
-    println!("{loops}");
+/// Walks from `pos` until the guard leaves the grid or loops, marking the
+/// directions each cell was crossed in. Returns whether it looped.
+fn walk(
+    obstacles: &[bool],
+    width: usize,
+    mut pos: usize,
+    mut direction: Direction,
+    obstruction: Option<usize>,
+    seen: &mut [u8],
+) -> bool {
+    let height = obstacles.len() / width;
+
+    loop {
+        if seen[pos] & direction.bit() != 0 {
+            return true;
+        }
+        seen[pos] |= direction.bit();
+
+        let (r, c) = (pos / width, pos % width);
+        let next = match direction {
+            Direction::Up => r.checked_sub(1).map(|r| r * width + c),
+            Direction::Down => (r + 1 < height).then_some(pos + width),
+            Direction::Left => c.checked_sub(1).map(|c| r * width + c),
+            Direction::Right => (c + 1 < width).then_some(pos + 1),
+        };
+        let Some(next) = next else {
+            return false;
+        };
+
+        if obstacles[next] || obstruction == Some(next) {
+            direction = direction.turn();
+        } else {
+            pos = next;
+        }
+    }
 }
 
 #[derive(Debug)]
-enum SimError {
+pub enum SimError {
     LoopDetected,
 }
 
@@ -59,6 +164,15 @@ impl Direction {
             Direction::Right => Direction::Down,
         }
     }
+
+    fn bit(&self) -> u8 {
+        match self {
+            Direction::Up => 1,
+            Direction::Down => 2,
+            Direction::Left => 4,
+            Direction::Right => 8,
+        }
+    }
 }
 
 impl From<char> for Tile {
@@ -119,40 +233,15 @@ enum Axis {
 }
 
 #[derive(Clone)]
-struct Map {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize,
+pub struct Map {
+    tiles: Grid<Tile>,
     print: bool,
 }
 
 impl Map {
-    fn from_lines(lines: impl Iterator<Item = impl AsRef<str>>) -> Self {
-        let mut tiles = Vec::new();
-        let mut h = 0;
-        let mut w = None;
-        for line in lines {
-            let line = line.as_ref();
-            h += 1;
-
-            if w.is_none() {
-                w = Some(line.len());
-            }
-
-            let mut row = Vec::new();
-            for c in line.chars() {
-                let tile: Tile = c.into();
-
-                row.push(tile);
-            }
-
-            tiles.push(row);
-        }
-
+    pub fn from_lines(lines: impl Iterator<Item = impl AsRef<str>>) -> Self {
         Self {
-            tiles,
-            width: w.unwrap(),
-            height: h,
+            tiles: Grid::from_lines(lines, Tile::from).expect("Invalid map"),
             print: false,
         }
     }
@@ -162,9 +251,10 @@ impl Map {
         self
     }
 
-    fn sim_path(&mut self) -> Result<&mut Self, SimError> {
+    /// Walks the guard until it leaves the map, marking the visited cells.
+    pub fn sim_path(&mut self) -> Result<&mut Self, SimError> {
         let Some((mut gr, mut gc)) = self.guard_pos() else {
-            println!("===========  ERROR  ===========\nInvalid board:");
+            error!("Invalid board:");
             self.print();
             self.print_map();
             panic!("No guard on map");
@@ -173,7 +263,7 @@ impl Map {
         loop {
             self.print_map();
             let ((lgr, lgc), encountered_obstacle, direction) = {
-                let direction = *self.tiles[gr][gc]
+                let direction = *self.tiles[(gr, gc)]
                     .guard_direction()
                     .expect("Guard not in expected position");
 
@@ -182,13 +272,13 @@ impl Map {
                         self.last_pos((0..=gr).rev(), Axis::Vertical(gc), direction)?
                     }
                     Direction::Down => {
-                        self.last_pos(gr..self.height, Axis::Vertical(gc), direction)?
+                        self.last_pos(gr..self.tiles.height(), Axis::Vertical(gc), direction)?
                     }
                     Direction::Left => {
                         self.last_pos((0..=gc).rev(), Axis::Horizontal(gr), direction)?
                     }
                     Direction::Right => {
-                        self.last_pos(gc..self.width, Axis::Horizontal(gr), direction)?
+                        self.last_pos(gc..self.tiles.width(), Axis::Horizontal(gr), direction)?
                     }
                 };
 
@@ -202,13 +292,13 @@ impl Map {
                 break;
             }
 
-            let old_tile = self.tiles[gr][gc].clone();
-            self.tiles[gr][gc] = Tile::Guard(direction.turn(), Box::new(old_tile));
+            let old_tile = self.tiles[(gr, gc)].clone();
+            self.tiles[(gr, gc)] = Tile::Guard(direction.turn(), Box::new(old_tile));
         }
 
-        if let Tile::Visited(_) = self.tiles[gr][gc] {
+        if let Tile::Visited(_) = self.tiles[(gr, gc)] {
         } else {
-            self.tiles[gr][gc] = Tile::Visited(HashSet::new());
+            self.tiles[(gr, gc)] = Tile::Visited(HashSet::new());
         }
 
         self.print_map();
@@ -220,20 +310,19 @@ impl Map {
             return;
         }
 
-        let mut map_str = String::with_capacity((self.width + 1) * self.height);
+        let mut map_str = String::with_capacity((self.tiles.width() + 1) * self.tiles.height());
 
-        for row in &self.tiles {
+        for row in self.tiles.rows() {
             for tile in row {
                 map_str.push(tile.into());
             }
             map_str.push('\n');
         }
 
-        println!();
-        println!("{map_str}");
+        trace!("\n{map_str}");
     }
 
-    fn find_loops(&mut self) -> usize {
+    pub fn find_loops(&mut self) -> usize {
         let start = self.guard_pos().expect("No guard on map");
         let mut loops = 0;
         let mut base = self.clone();
@@ -241,40 +330,50 @@ impl Map {
         base.sim_path().expect("Found loop in base map");
 
         let mut tested = 0;
-        for r in 0..self.width {
-            for c in 0..self.height {
-                if (r, c) == start {
-                    continue;
-                }
-
-                let tile = &base.tiles[r][c];
-
-                if let Tile::Visited(_) = tile {
-                    let mut testee = self.clone();
-                    testee.tiles[r][c] = Tile::Obstacle;
-                    //if tested == 496 {
-                    //    testee.print();
-                    //}
-                    if testee.sim_path().is_err() {
-                        loops += 1;
-                        println!("{tested}: true");
-                    } else {
-                        println!("{tested}: false");
-                    }
+        for (pos, tile) in base.tiles.cells() {
+            if pos == start {
+                continue;
+            }
 
-                    tested += 1;
+            if let Tile::Visited(_) = tile {
+                let mut testee = self.clone();
+                testee.tiles[pos] = Tile::Obstacle;
+                //if tested == 496 {
+                //    testee.print();
+                //}
+                if testee.sim_path().is_err() {
+                    loops += 1;
+                    debug!("{tested}: true");
+                } else {
+                    debug!("{tested}: false");
                 }
+
+                tested += 1;
             }
         }
 
         loops
     }
 
-    fn visited(&self) -> usize {
+    /// The map with the cells the guard has visited marked.
+    pub fn visualisation(&self) -> Visualisation {
+        Visualisation {
+            caption: "cells the guard visits".to_string(),
+            rows: self.tiles.lines(|tile| tile.into()),
+            marked: self
+                .tiles
+                .cells()
+                .filter(|(_, tile)| tile.visited())
+                .map(|(pos, _)| pos)
+                .collect(),
+        }
+    }
+
+    pub fn visited(&self) -> usize {
         self.tiles
-            .iter()
-            .map(|row| row.iter().filter(|tile| tile.visited()).count())
-            .sum()
+            .cells()
+            .filter(|(_, tile)| tile.visited())
+            .count()
     }
 
     fn last_pos(
@@ -287,7 +386,7 @@ impl Map {
             Axis::Horizontal(gr) => {
                 let positive = direction == Direction::Right;
                 for c in path {
-                    let tile = &mut self.tiles[gr][c];
+                    let tile = &mut self.tiles[(gr, c)];
 
                     match tile {
                         Tile::Obstacle => {
@@ -302,7 +401,7 @@ impl Map {
                             }
                         }
                         Tile::Guard(_, tile) => {
-                            self.tiles[gr][c] = if let Tile::Visited(dirs) = tile.as_mut() {
+                            self.tiles[(gr, c)] = if let Tile::Visited(dirs) = tile.as_mut() {
                                 dirs.insert(direction);
                                 *tile.clone()
                             } else {
@@ -310,13 +409,13 @@ impl Map {
                             }
                         }
                         _ => {
-                            self.tiles[gr][c] = Tile::Visited(HashSet::from([direction]));
+                            self.tiles[(gr, c)] = Tile::Visited(HashSet::from([direction]));
                         }
                     }
                 }
 
                 if positive {
-                    Ok(((gr, self.width - 1), false))
+                    Ok(((gr, self.tiles.width() - 1), false))
                 } else {
                     Ok(((gr, 0), false))
                 }
@@ -324,7 +423,7 @@ impl Map {
             Axis::Vertical(gc) => {
                 let positive = direction == Direction::Down;
                 for r in path {
-                    let tile = &mut self.tiles[r][gc];
+                    let tile = &mut self.tiles[(r, gc)];
 
                     match tile {
                         Tile::Obstacle => {
@@ -339,7 +438,7 @@ impl Map {
                             }
                         }
                         Tile::Guard(_, tile) => {
-                            self.tiles[r][gc] = if let Tile::Visited(dirs) = tile.as_mut() {
+                            self.tiles[(r, gc)] = if let Tile::Visited(dirs) = tile.as_mut() {
                                 dirs.insert(direction);
                                 *tile.clone()
                             } else {
@@ -347,13 +446,13 @@ impl Map {
                             }
                         }
                         _ => {
-                            self.tiles[r][gc] = Tile::Visited(HashSet::from([direction]));
+                            self.tiles[(r, gc)] = Tile::Visited(HashSet::from([direction]));
                         }
                     }
                 }
 
                 if positive {
-                    Ok(((self.height - 1, gc), false))
+                    Ok(((self.tiles.height() - 1, gc), false))
                 } else {
                     Ok(((0, gc), false))
                 }
@@ -361,22 +460,17 @@ impl Map {
         }
     }
 
-    fn guard_pos(&self) -> Option<(usize, usize)> {
-        for (r, row) in self.tiles.iter().enumerate() {
-            for (c, tile) in row.iter().enumerate() {
-                if let Tile::Guard(_, _) = tile {
-                    return Some((r, c));
-                }
-            }
-        }
-
-        None
+    fn guard_pos(&self) -> Option<Pos> {
+        self.tiles
+            .position(|tile| matches!(tile, Tile::Guard(_, _)))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Map;
+    use super::{loop_count_grid_walk, Map};
+
+    crate::example_tests!(super::Day6);
 
     #[test]
     fn count_visited_test_data() {
@@ -417,4 +511,22 @@ mod test {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn count_loops_grid_walk() {
+        let input = r#"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."#;
+        let expected = 6;
+        let actual = loop_count_grid_walk(&Map::from_lines(input.lines()));
+
+        assert_eq!(actual, expected);
+    }
 }
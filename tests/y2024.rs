@@ -0,0 +1,43 @@
+use advent_of_code::{
+    y2024::{day02, day03, day04::WordSearch, day05, day06, day06::Map},
+    Answer, Solution,
+};
+
+#[test]
+fn day02_is_safe() {
+    assert!(day02::is_safe(&[7, 6, 4, 2, 1]));
+    assert!(!day02::is_safe(&[1, 2, 7, 8, 9]));
+}
+
+#[test]
+fn day03_eval() {
+    let memory = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    assert_eq!(day03::eval(memory, false), 161);
+    assert_eq!(day03::eval(memory, true), 48);
+}
+
+#[test]
+fn day04_word_search() {
+    let word_search = WordSearch::new(include_bytes!("../input/2024/examples/day04/example.txt"));
+
+    assert_eq!(word_search.count_xmas(), 18);
+    assert_eq!(word_search.count_mas_x(), 9);
+}
+
+#[test]
+fn day05_manual() {
+    let manual = day05::parse(include_str!("../input/2024/examples/day05/example.txt").lines());
+
+    assert_eq!(day05::count(&manual), 143);
+    assert_eq!(day05::sort_and_sum(&manual), 123);
+}
+
+#[test]
+fn day06_map() {
+    let map = Map::from_lines(include_str!("../input/2024/examples/day06/example.txt").lines());
+
+    assert_eq!(day06::path_count(map.clone()), 41);
+    assert_eq!(day06::loop_count_grid_walk(&map), 6);
+    assert_eq!(day06::Day6::part2(&map).into(), Answer::from(6));
+}